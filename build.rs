@@ -1,4 +1,358 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
 fn main() {
+    println!("cargo:rerun-if-changed=csrc");
+    println!("cargo:rerun-if-changed=src/c_api.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
     cc::Build::new().file("csrc/rax.c").include("csrc").compile("rax");
+
+    let c_api = fs::read_to_string("src/c_api.rs").expect("failed to read src/c_api.rs");
+    let header = generate_header(&c_api);
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() == "linux" {
+        version_symbols(&c_api, &out_dir);
+    }
+    fs::write(out_dir.join(HEADER_NAME), &header).expect("failed to write C header");
+    // OUT_DIR is target/<profile>/build/<package>-<hash>/out.
+    if let Some(profile_dir) = out_dir.ancestors().nth(3) {
+        write_if_changed(&profile_dir.join("include").join(HEADER_NAME), &header);
+        write_if_changed(
+            &profile_dir.join("pkgconfig").join("radix_route_matcher.pc"),
+            &pkg_config(profile_dir),
+        );
+    }
+}
+
+const HEADER_NAME: &str = "radix_route_matcher.h";
+
+fn write_if_changed(path: &Path, contents: &str) {
+    if fs::read_to_string(path).is_ok_and(|old| old == contents) {
+        return;
+    }
+    fs::create_dir_all(path.parent().unwrap()).expect("failed to create include directory");
+    fs::write(path, contents).expect("failed to write C header");
+}
+
+const VERSION_SCRIPT: &str = "csrc/radix_route_matcher.map";
+
+/// Environment variable enabling the symbol versions of `csrc/radix_route_matcher.map`.
+const SYMBOL_VERSIONS_ENV: &str = "RADIX_ROUTE_MATCHER_SYMBOL_VERSIONS";
+
+/// Links the cdylib with the symbol versions of `csrc/radix_route_matcher.map`, when
+/// `RADIX_ROUTE_MATCHER_SYMBOL_VERSIONS=1`.
+///
+/// rustc already passes an anonymous version script of its own, which GNU ld refuses to
+/// combine with named versions, and gold and lld silently prefer. The linker is therefore
+/// tried on a small probe library first, and the build fails if the probe does not end up
+/// with the version of the current ABI.
+fn version_symbols(c_api: &str, out_dir: &Path) {
+    println!("cargo:rerun-if-env-changed={}", SYMBOL_VERSIONS_ENV);
+    if env::var(SYMBOL_VERSIONS_ENV).as_deref() != Ok("1") {
+        return;
+    }
+    let abi = c_api
+        .lines()
+        .find_map(|line| line.strip_prefix("pub const RADIX_TREE_ABI_VERSION: c_int = "))
+        .expect("RADIX_TREE_ABI_VERSION not found")
+        .trim_end_matches(';');
+    let script = Path::new(env!("CARGO_MANIFEST_DIR")).join(VERSION_SCRIPT);
+    let nodes = fs::read_to_string(&script).expect("failed to read the version script");
+    let version = format!("RADIX_ROUTE_MATCHER_{}", abi);
+    assert!(
+        nodes.contains(&format!("{} {{", version)),
+        "{} has no {} node for the current ABI version",
+        VERSION_SCRIPT,
+        version
+    );
+
+    let option = format!("-Wl,--version-script={}", script.display());
+    if !linker_applies(&option, &version, out_dir) {
+        panic!(
+            "{}=1, but the linker does not apply {} next to the version script of rustc \
+             (GNU ld rejects the combination, gold and lld ignore it): configure a linker \
+             that keeps only the library version script, or unset {}",
+            SYMBOL_VERSIONS_ENV, VERSION_SCRIPT, SYMBOL_VERSIONS_ENV
+        );
+    }
+    println!("cargo:rustc-cdylib-link-arg={}", option);
+}
+
+/// Links a probe cdylib with the link argument `option`, as the library is linked, and
+/// returns whether it defines the symbol version `version`.
+fn linker_applies(option: &str, version: &str, out_dir: &Path) -> bool {
+    let probe_dir = out_dir.join("symver-probe");
+    fs::create_dir_all(&probe_dir).expect("failed to create the probe directory");
+    let source = probe_dir.join("probe.rs");
+    fs::write(&source, "#[no_mangle]\npub extern \"C\" fn radix_tree_probe() {}\n")
+        .expect("failed to write the probe library");
+    let mut rustc = Command::new(env::var("RUSTC").unwrap());
+    rustc
+        .args(["--crate-type", "cdylib", "--crate-name", "probe", "--target"])
+        .arg(env::var("TARGET").unwrap())
+        .arg("--out-dir")
+        .arg(&probe_dir)
+        .arg(format!("-Clink-arg={}", option))
+        .arg(&source);
+    if let Ok(linker) = env::var("RUSTC_LINKER") {
+        rustc.arg(format!("-Clinker={}", linker));
+    }
+    if let Ok(flags) = env::var("CARGO_ENCODED_RUSTFLAGS") {
+        rustc.args(flags.split('\x1f').filter(|flag| !flag.is_empty()));
+    }
+    rustc.output().is_ok_and(|output| output.status.success())
+        && fs::read(probe_dir.join("libprobe.so"))
+            .is_ok_and(|lib| symbol_version(&lib, "radix_tree_probe") == Some(version.to_string()))
 }
 
+/// Returns the version an ELF shared library defines its dynamic symbol `name` with, `None`
+/// if the symbol is missing or unversioned.
+///
+/// lld, for one, defines the versions of the library version script but leaves the symbols
+/// with the base version of rustc's script, so the tables are read rather than the names.
+fn symbol_version(elf: &[u8], name: &str) -> Option<String> {
+    const SHT_DYNSYM: u32 = 11;
+    const SHT_GNU_VERDEF: u32 = 0x6fff_fffd;
+    const SHT_GNU_VERSYM: u32 = 0x6fff_ffff;
+
+    if elf.get(..4)? != b"\x7fELF" {
+        return None;
+    }
+    let wide = *elf.get(4)? == 2;
+    let big_endian = *elf.get(5)? == 2;
+    let uint = |at: usize, len: usize| -> Option<usize> {
+        let bytes = elf.get(at..at.checked_add(len)?)?;
+        let fold = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+        let value = if big_endian {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        };
+        usize::try_from(value).ok()
+    };
+    let word = if wide { 8 } else { 4 };
+    let string = |table: usize, at: usize| -> Option<&[u8]> {
+        let start = table.checked_add(at)?;
+        let len = elf.get(start..)?.iter().position(|&byte| byte == 0)?;
+        elf.get(start..start + len)
+    };
+
+    // (type, offset, size, offset of the linked section)
+    let (shoff, shentsize, shnum) = if wide {
+        (uint(0x28, 8)?, uint(0x3a, 2)?, uint(0x3c, 2)?)
+    } else {
+        (uint(0x20, 4)?, uint(0x2e, 2)?, uint(0x30, 2)?)
+    };
+    let header = |index: usize| -> Option<(u32, usize, usize, usize)> {
+        let at = shoff.checked_add(index.checked_mul(shentsize)?)?;
+        let (offset, size, link) = if wide { (0x18, 0x20, 0x28) } else { (0x10, 0x14, 0x18) };
+        Some((
+            uint(at + 4, 4)? as u32,
+            uint(at + offset, word)?,
+            uint(at + size, word)?,
+            uint(at + link, 4)?,
+        ))
+    };
+    let sections: Vec<_> = (0..shnum).map(header).collect::<Option<_>>()?;
+    let section = |kind: u32| sections.iter().find(|section| section.0 == kind);
+    let strtab = |link: usize| sections.get(link).map(|section| section.1);
+
+    let (_, dynsym, dynsym_size, dynsym_link) = *section(SHT_DYNSYM)?;
+    let (_, versym, _, _) = *section(SHT_GNU_VERSYM)?;
+    let (_, verdef, verdef_size, verdef_link) = *section(SHT_GNU_VERDEF)?;
+    let dynstr = strtab(dynsym_link)?;
+    let entsize = if wide { 24 } else { 16 };
+    let index = (0..dynsym_size / entsize).find(|index| {
+        string(dynstr, uint(dynsym + index * entsize, 4).unwrap_or(usize::MAX)) == Some(name.as_bytes())
+    })?;
+    let ndx = uint(versym + index * 2, 2)? & 0x7fff;
+
+    let verstr = strtab(verdef_link)?;
+    let mut at = verdef;
+    while at < verdef + verdef_size {
+        if uint(at + 4, 2)? == ndx {
+            let aux = at + uint(at + 12, 4)?;
+            return String::from_utf8(string(verstr, uint(aux, 4)?)?.to_vec()).ok();
+        }
+        match uint(at + 16, 4)? {
+            0 => break,
+            next => at += next,
+        }
+    }
+    None
+}
+
+/// Generates a pkg-config file for the cdylib, as laid out in the target directory.
+fn pkg_config(profile_dir: &Path) -> String {
+    format!(
+        "prefix={}\n\
+         libdir=${{prefix}}\n\
+         includedir=${{prefix}}/include\n\
+         \n\
+         Name: radix_route_matcher\n\
+         Description: {}\n\
+         Version: {}\n\
+         Libs: -L${{libdir}} -lradix_route_matcher\n\
+         Cflags: -I${{includedir}}\n",
+        profile_dir.display(),
+        env::var("CARGO_PKG_DESCRIPTION").unwrap(),
+        env::var("CARGO_PKG_VERSION").unwrap(),
+    )
+}
+
+/// Maps a Rust FFI type used in `src/c_api.rs` to its C spelling.
+fn c_type(ty: &str) -> String {
+    match ty.trim() {
+        "*mut c_void" => "void *".to_string(),
+        "*const c_void" => "const void *".to_string(),
+        "*const c_uchar" => "const unsigned char *".to_string(),
+        "*mut c_uchar" => "unsigned char *".to_string(),
+        "*const c_char" => "const char *".to_string(),
+        "c_ulong" => "unsigned long".to_string(),
+        "c_int" => "int".to_string(),
+        "*const c_ulong" => "const unsigned long *".to_string(),
+        "*mut c_ulong" => "unsigned long *".to_string(),
+        "*const c_int" => "const int *".to_string(),
+        "*mut c_int" => "int *".to_string(),
+        "*const *const c_uchar" => "const unsigned char *const *".to_string(),
+        // Opaque handle types.
+        "*mut radix_tree_t" => "radix_tree_t *".to_string(),
+        "*mut radix_iter_t" => "radix_iter_t *".to_string(),
+        // Callback types.
+        "radix_tree_foreach_cb" => "radix_tree_foreach_cb".to_string(),
+        other => panic!("unsupported type in C API: {}", other),
+    }
+}
+
+/// Splits a Rust signature (`name(a: T, ...) -> R`, the name being optional) into its name,
+/// C return type and C parameter list.
+fn c_signature(signature: &str) -> (&str, String, String) {
+    let (name, rest) = signature.split_once('(').unwrap();
+    let (params, rest) = rest.rsplit_once(')').unwrap();
+    let ret = rest.trim_end_matches('{').trim();
+    let ret = ret.strip_prefix("->").map_or("void".to_string(), c_type);
+    let params: Vec<String> = params
+        .split(',')
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| {
+            let (name, ty) = p.split_once(':').unwrap();
+            let ty = c_type(ty);
+            let sep = if ty.ends_with('*') { "" } else { " " };
+            format!("{}{}{}", ty, sep, name.trim())
+        })
+        .collect();
+    let params = if params.is_empty() {
+        "void".to_string()
+    } else {
+        params.join(", ")
+    };
+    (name.trim(), ret, params)
+}
+
+/// Writes doc comment lines as a C block comment.
+fn comment(out: &mut String, lines: &[String]) {
+    out.push_str("/*\n");
+    for line in lines {
+        if line.is_empty() {
+            out.push_str(" *\n");
+        } else {
+            out.push_str(" * ");
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out.push_str(" */\n");
+}
+
+/// Generates the C header from the `#[no_mangle]` functions, public constants, callback
+/// types and opaque public structs of `src/c_api.rs`, with their doc comments. The module
+/// documentation becomes the header preamble.
+fn generate_header(src: &str) -> String {
+    let doc = |line: &str, marker: &str| {
+        line.strip_prefix(marker)
+            .map(|d| d.strip_prefix(' ').unwrap_or(d).to_string())
+    };
+
+    let mut out = String::new();
+    out.push_str("/* Generated by build.rs from src/c_api.rs. Do not edit. */\n\n");
+    let module_doc: Vec<String> = src.lines().map_while(|line| doc(line, "//!")).collect();
+    comment(&mut out, &module_doc);
+    out.push_str("\n#ifndef RADIX_ROUTE_MATCHER_H\n#define RADIX_ROUTE_MATCHER_H\n\n");
+    out.push_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n");
+
+    let mut docs = Vec::new();
+    let mut lines = src.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if let Some(d) = doc(line, "///") {
+            docs.push(d);
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("pub struct ") {
+            let name = name.trim_end_matches(['{', ' ']);
+            out.push('\n');
+            comment(&mut out, &docs);
+            out.push_str(&format!("typedef struct {} {};\n", name, name));
+            docs.clear();
+            continue;
+        }
+        if let Some(constant) = line.strip_prefix("pub const ") {
+            let (name, rest) = constant.split_once(':').unwrap();
+            let (_, value) = rest.split_once('=').unwrap();
+            out.push('\n');
+            comment(&mut out, &docs);
+            out.push_str(&format!("#define {} ({})\n", name, value.trim().trim_end_matches(';')));
+            docs.clear();
+            continue;
+        }
+        if let Some(alias) = line.strip_prefix("pub type ") {
+            // Callbacks: `pub type name = Option<unsafe extern "C" fn(...) -> ...>;`
+            let mut alias = alias.to_string();
+            while !alias.ends_with(';') {
+                alias.push_str(lines.next().unwrap().trim());
+            }
+            let (name, function) = alias.split_once('=').unwrap();
+            let name = name.trim();
+            let function = function.trim();
+            let function = function
+                .strip_prefix("Option<unsafe extern \"C\" fn")
+                .and_then(|f| f.strip_suffix(">;"))
+                .expect("C API callbacks must be `Option<unsafe extern \"C\" fn(...)>`");
+            let (_, ret, params) = c_signature(function);
+            out.push('\n');
+            comment(&mut out, &docs);
+            out.push_str(&format!("typedef {} (*{})({});\n", ret, name, params));
+            docs.clear();
+            continue;
+        }
+        if line.starts_with("#[") && line != "#[no_mangle]" {
+            continue;
+        }
+        if line != "#[no_mangle]" {
+            docs.clear();
+            continue;
+        }
+        let mut signature = String::new();
+        for line in lines.by_ref() {
+            signature.push_str(line.trim());
+            if line.trim_end().ends_with('{') {
+                break;
+            }
+        }
+        let signature = signature
+            .strip_prefix("pub extern \"C\" fn ")
+            .expect("C API functions must be `pub extern \"C\" fn`");
+        let (name, ret, params) = c_signature(signature);
+
+        out.push('\n');
+        comment(&mut out, &docs);
+        let sep = if ret.ends_with('*') { "" } else { " " };
+        out.push_str(&format!("{}{}{}({});\n", ret, sep, name, params));
+        docs.clear();
+    }
+
+    out.push_str("\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* RADIX_ROUTE_MATCHER_H */\n");
+    out
+}
@@ -1945,3 +1945,85 @@ unsigned long raxTouch(raxNode *n) {
     }
     return sum;
 }
+
+/* --------------------------- Low level node access ------------------------ */
+
+/* The following functions expose the node layout to the Rust side, which
+ * cannot portably access the raxNode bitfields. They are used in order to
+ * walk the tree without an iterator, and to rebuild a tree node by node
+ * (for instance when loading a snapshot) without paying for raxInsert(). */
+
+int raxNodeIsKey(raxNode *n) {
+    return n->iskey;
+}
+
+int raxNodeIsNull(raxNode *n) {
+    return n->isnull;
+}
+
+int raxNodeIsCompr(raxNode *n) {
+    return n->iscompr;
+}
+
+size_t raxNodeSize(raxNode *n) {
+    return n->size;
+}
+
+unsigned char *raxNodeChars(raxNode *n) {
+    return n->data;
+}
+
+/* Return the number of bytes allocated for the node. */
+size_t raxNodeLength(raxNode *n) {
+    return raxNodeCurrentLength(n);
+}
+
+/* Return the i-th child of the node. Compressed nodes only have child 0. */
+raxNode *raxNodeChild(raxNode *n, size_t i) {
+    raxNode *child;
+    raxNode **cp = raxNodeFirstChildPtr(n) + i;
+    memcpy(&child,cp,sizeof(child));
+    return child;
+}
+
+/* Set the i-th child of the node. */
+void raxNodeSetChild(raxNode *n, size_t i, raxNode *child) {
+    raxNode **cp = raxNodeFirstChildPtr(n) + i;
+    memcpy(cp,&child,sizeof(child));
+}
+
+/* Allocate a node with the specified layout in a single allocation. The
+ * 'size' characters are copied from 'chars' and all the child pointers are
+ * set to NULL, so that the caller can fill them with raxNodeSetChild().
+ * If 'iskey' is true the node is a key, with 'data' as associated value.
+ * On out of memory NULL is returned. */
+raxNode *raxNodeCreate(int iscompr, size_t size, unsigned char *chars, int iskey, void *data) {
+    if (size > RAX_NODE_MAX_SIZE) return NULL;
+    size_t children = iscompr ? 1 : size;
+    size_t nodesize = sizeof(raxNode)+size+raxPadding(size)+
+                      sizeof(raxNode*)*children;
+    if (iskey && data != NULL) nodesize += sizeof(void*);
+    raxNode *node = rax_malloc(nodesize);
+    if (node == NULL) return NULL;
+    node->iskey = 0;
+    node->isnull = 0;
+    node->iscompr = iscompr != 0;
+    node->size = size;
+    if (size) memcpy(node->data,chars,size);
+    memset(raxNodeFirstChildPtr(node),0,sizeof(raxNode*)*children);
+    if (iskey) raxSetData(node,data);
+    return node;
+}
+
+/* Free a node and all its descendants. Unlike raxRecursiveFree() this does
+ * not require a rax, and NULL children are skipped, so that it can be used to
+ * release a partially built subtree. Returns the number of freed nodes. */
+uint64_t raxNodeFree(raxNode *n) {
+    if (n == NULL) return 0;
+    uint64_t freed = 1;
+    size_t numchildren = n->iscompr ? 1 : n->size;
+    for (size_t i = 0; i < numchildren; i++)
+        freed += raxNodeFree(raxNodeChild(n,i));
+    rax_free(n);
+    return freed;
+}
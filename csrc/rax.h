@@ -212,5 +212,18 @@ void raxSetDebugMsg(int onoff);
 /* Internal API. May be used by the node callback in order to access rax nodes
  * in a low level way, so this function is exported as well. */
 void raxSetData(raxNode *n, void *data);
+void *raxGetData(raxNode *n);
+
+/* Low level node access, used to walk or rebuild a tree node by node. */
+int raxNodeIsKey(raxNode *n);
+int raxNodeIsNull(raxNode *n);
+int raxNodeIsCompr(raxNode *n);
+size_t raxNodeSize(raxNode *n);
+unsigned char *raxNodeChars(raxNode *n);
+size_t raxNodeLength(raxNode *n);
+raxNode *raxNodeChild(raxNode *n, size_t i);
+void raxNodeSetChild(raxNode *n, size_t i, raxNode *child);
+raxNode *raxNodeCreate(int iscompr, size_t size, unsigned char *chars, int iskey, void *data);
+uint64_t raxNodeFree(raxNode *n);
 
 #endif
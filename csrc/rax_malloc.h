@@ -33,11 +33,25 @@
  * This file is used in order to change the Rax allocator at compile time.
  * Just define the following defines to what you want to use. Also add
  * the include of your alternate allocator if needed (not needed in order
- * to use the default libc allocator). */
+ * to use the default libc allocator).
+ *
+ * Here allocations go through the Rust global allocator, using the hooks
+ * defined in src/alloc.rs, which also account for the memory of each tree.
+ * The hooks are internal to the library: hidden visibility keeps them out of
+ * the symbols exported by the shared library. */
 
 #ifndef RAX_ALLOC_H
 #define RAX_ALLOC_H
-#define rax_malloc malloc
-#define rax_realloc realloc
-#define rax_free free
+#include <stddef.h>
+#if defined(__GNUC__) || defined(__clang__)
+#define RAX_RUST_HIDDEN __attribute__((visibility("hidden")))
+#else
+#define RAX_RUST_HIDDEN
+#endif
+RAX_RUST_HIDDEN void *rax_rust_malloc(size_t size);
+RAX_RUST_HIDDEN void *rax_rust_realloc(void *ptr, size_t size);
+RAX_RUST_HIDDEN void rax_rust_free(void *ptr);
+#define rax_malloc rax_rust_malloc
+#define rax_realloc rax_rust_realloc
+#define rax_free rax_rust_free
 #endif
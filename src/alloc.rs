@@ -0,0 +1,144 @@
+//! Allocator hooks for the embedded rax C code
+//!
+//! `csrc/rax_malloc.h` maps `rax_malloc`, `rax_realloc` and `rax_free` to the functions of
+//! this model, so that tree memory is allocated through the Rust global allocator (and
+//! whichever `#[global_allocator]` the application installs) instead of libc.
+//!
+//! Every block starts with a 16-byte header recording its size, which the Rust allocator
+//! needs back on free() but C callers do not pass, and the counter it is charged to.
+//! Blocks allocated inside [`charge_to`] are charged to its counter, which is how each
+//! tree accounts for the memory of its nodes; other blocks (such as iterator buffers) are
+//! not charged to anything. The header is a real cost for small nodes: a leaf or a short
+//! compressed node takes a few dozen bytes, so the header can add a third or more to it.
+//! Counters only include the requested sizes.
+//!
+//! The hooks are only meant for the rax code linked into this crate: they are not part of
+//! the C API, and `rax_malloc.h` declares them with hidden visibility so that the shared
+//! library does not export them.
+
+use libc::{c_void, size_t};
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Size of the header in front of every block. It keeps blocks aligned like malloc() does.
+const HEADER_LEN: usize = 16;
+const ALIGN: usize = 16;
+
+#[repr(C)]
+struct Header {
+    size: usize,
+    counter: *const AtomicUsize,
+}
+
+const _: () = assert!(std::mem::size_of::<Header>() <= HEADER_LEN);
+
+thread_local! {
+    static CURRENT: Cell<*const AtomicUsize> = const { Cell::new(ptr::null()) };
+}
+
+/// Runs `f`, charging the blocks it allocates on this thread to `counter`.
+///
+/// Blocks keep their counter when reallocated, and give their size back to it when freed:
+/// the counter must outlive every block charged to it.
+pub(crate) fn charge_to<R>(counter: &AtomicUsize, f: impl FnOnce() -> R) -> R {
+    struct Restore(*const AtomicUsize);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(counter)));
+    f()
+}
+
+/// Returns the size requested for a block allocated by [`rax_rust_malloc`] or
+/// [`rax_rust_realloc`].
+///
+/// # Safety
+///
+/// `ptr` must be a live block returned by one of these functions.
+pub(crate) unsafe fn block_size(ptr: *const c_void) -> usize {
+    (*ptr.cast::<u8>().sub(HEADER_LEN).cast::<Header>()).size
+}
+
+fn layout(size: usize) -> Option<Layout> {
+    Layout::from_size_align(size.checked_add(HEADER_LEN)?, ALIGN).ok()
+}
+
+/// Allocates a block of `size` bytes, like malloc().
+///
+/// # Safety
+///
+/// The block must be released with [`rax_rust_free`].
+#[no_mangle]
+pub(crate) unsafe extern "C" fn rax_rust_malloc(size: size_t) -> *mut c_void {
+    let Some(layout) = layout(size) else {
+        return ptr::null_mut();
+    };
+    let base = alloc::alloc(layout);
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+    let counter = CURRENT.with(Cell::get);
+    base.cast::<Header>().write(Header { size, counter });
+    if let Some(counter) = counter.as_ref() {
+        counter.fetch_add(size, Ordering::Relaxed);
+    }
+    base.add(HEADER_LEN).cast()
+}
+
+/// Resizes a block, like realloc(). On failure NULL is returned and the block is untouched.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or a live block returned by [`rax_rust_malloc`] or [`rax_rust_realloc`].
+#[no_mangle]
+pub(crate) unsafe extern "C" fn rax_rust_realloc(ptr: *mut c_void, size: size_t) -> *mut c_void {
+    if ptr.is_null() {
+        return rax_rust_malloc(size);
+    }
+    let Some(new_layout) = layout(size) else {
+        return ptr::null_mut();
+    };
+    let base = ptr.cast::<u8>().sub(HEADER_LEN);
+    let Header {
+        size: old_size,
+        counter,
+    } = base.cast::<Header>().read();
+    let old_layout = Layout::from_size_align_unchecked(old_size + HEADER_LEN, ALIGN);
+    let base = alloc::realloc(base, old_layout, new_layout.size());
+    if base.is_null() {
+        return ptr::null_mut();
+    }
+    base.cast::<Header>().write(Header { size, counter });
+    if let Some(counter) = counter.as_ref() {
+        if size > old_size {
+            counter.fetch_add(size - old_size, Ordering::Relaxed);
+        } else {
+            counter.fetch_sub(old_size - size, Ordering::Relaxed);
+        }
+    }
+    base.add(HEADER_LEN).cast()
+}
+
+/// Releases a block, like free().
+///
+/// # Safety
+///
+/// `ptr` must be NULL or a live block returned by [`rax_rust_malloc`] or [`rax_rust_realloc`].
+#[no_mangle]
+pub(crate) unsafe extern "C" fn rax_rust_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    let base = ptr.cast::<u8>().sub(HEADER_LEN);
+    let Header { size, counter } = base.cast::<Header>().read();
+    alloc::dealloc(base, Layout::from_size_align_unchecked(size + HEADER_LEN, ALIGN));
+    if let Some(counter) = counter.as_ref() {
+        counter.fetch_sub(size, Ordering::Relaxed);
+    }
+}
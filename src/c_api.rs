@@ -2,9 +2,390 @@
 //!
 //! This model exports C-compatible functions that can be called from C/C++ or other languages
 //! through FFI. These functions provide the same functionality as the Rust API but with a C ABI.
+//!
+//! The C header `radix_route_matcher.h` is generated from this file by `build.rs`.
+//!
+//! # Ownership
+//!
+//! - A tree returned by radix_tree_new() is owned by the caller and released with
+//!   radix_tree_destroy().
+//! - An iterator returned by radix_tree_new_it() is owned by the caller and released with
+//!   radix_tree_free_it(), which stops and frees it in one call.
+//! - Iterators may outlive their tree: after radix_tree_destroy() the tree memory is
+//!   released, operations on its remaining iterators fail, and radix_tree_free_it() is
+//!   still required for each of them.
+//! - Key buffers are only borrowed for the duration of a call.
+//!
+//! # Handles
+//!
+//! Trees and iterators are distinct opaque types (`radix_tree_t` and `radix_iter_t`), so
+//! that mixing them up is a compile error in C. Debug builds of the library also tag each
+//! handle with a magic number: functions given the wrong kind of handle, or a destroyed
+//! one, fail with RADIX_TREE_EBADHANDLE (or NULL) instead of crashing. To that end, debug
+//! builds keep the handle structs of the last 1024 destroyed trees and iterators allocated,
+//! so that they can still be read; older ones are freed, and their use is no longer
+//! detected.
+//!
+//! # Versioning
+//!
+//! RADIX_TREE_ABI_VERSION is incremented on every incompatible change to this API. Callers
+//! loading the library at run time (e.g. with dlopen() or LuaJIT's ffi.load()) should
+//! check that radix_tree_abi_version() returns the value they were built against, and can
+//! probe optional capabilities with radix_tree_features().
+//!
+//! On Linux, a library built with RADIX_ROUTE_MATCHER_SYMBOL_VERSIONS=1 also gives its
+//! exports an ELF symbol version, RADIX_ROUTE_MATCHER_<abi version>, so that a program
+//! linked against an incompatible library fails to load rather than call functions with
+//! the wrong contract.
+//!
+//! # Errors
+//!
+//! Functions returning `int` report errors with a negative code, and functions returning
+//! a pointer with NULL. The reason is then available from radix_tree_last_error(), on
+//! the same thread. A NULL key buffer is accepted only with a zero length. Panics never
+//! unwind across the C ABI: they are reported as RADIX_TREE_EPANIC.
 
+use crate::alloc::charge_to;
 use crate::ffi::*;
-use libc::{c_int, c_uchar, c_ulong, c_void};
+use libc::{c_char, c_int, c_uchar, c_ulong, c_void};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Version of this API, incremented on every incompatible change.
+pub const RADIX_TREE_ABI_VERSION: c_int = 1;
+
+/// Feature flag: handles are checked at run time (debug builds).
+pub const RADIX_TREE_FEATURE_HANDLE_CHECKS: c_ulong = 1 << 0;
+/// Feature flag: built with the `serde` feature.
+pub const RADIX_TREE_FEATURE_SERDE: c_ulong = 1 << 1;
+/// Feature flag: built with the `json` feature.
+pub const RADIX_TREE_FEATURE_JSON: c_ulong = 1 << 2;
+/// Feature flag: built with the `yaml` feature.
+pub const RADIX_TREE_FEATURE_YAML: c_ulong = 1 << 3;
+/// Feature flag: built with the `toml` feature.
+pub const RADIX_TREE_FEATURE_TOML: c_ulong = 1 << 4;
+/// Feature flag: built with the `idna` feature.
+pub const RADIX_TREE_FEATURE_IDNA: c_ulong = 1 << 5;
+
+/// Error code returned when an argument is invalid, such as a NULL buf with a non-zero len.
+pub const RADIX_TREE_EINVAL: c_int = -2;
+
+/// Error code returned when a handle does not have the expected type (an iterator passed
+/// as a tree, or the opposite), or was already destroyed.
+///
+/// Handles are only checked in debug builds.
+pub const RADIX_TREE_EBADHANDLE: c_int = -3;
+
+/// Error code returned when the library hit an internal error (a Rust panic).
+pub const RADIX_TREE_EPANIC: c_int = -4;
+
+const TREE_MAGIC: u32 = 0x5254_5245; // "RTRE"
+const ITER_MAGIC: u32 = 0x5249_5452; // "RITR"
+const DEAD_MAGIC: u32 = 0xDEAD_0000;
+
+/// Opaque handle to a radix tree.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct radix_tree_t {
+    magic: u32,
+    rax: *mut c_void,
+    /// One reference for the tree itself, plus one per live iterator.
+    refs: AtomicUsize,
+    destroyed: AtomicBool,
+    /// Bytes allocated for the nodes of the tree.
+    allocated: AtomicUsize,
+}
+
+/// Opaque handle to a radix tree iterator.
+///
+/// Handles are allocated with malloc(), which keeps the legacy release sequence
+/// (radix_tree_stop() followed by free()) working.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+pub struct radix_iter_t {
+    magic: u32,
+    raw: RaxIterator,
+    tree: *mut radix_tree_t,
+    stopped: bool,
+}
+
+/// Callback invoked by radix_tree_foreach() and radix_tree_foreach_prefix() for each
+/// matching key, with the key bytes (not NUL-terminated), the key length, the associated
+/// value and the caller's userdata.
+///
+/// Returning non-zero stops the iteration. The key is only valid during the call.
+#[allow(non_camel_case_types)]
+pub type radix_tree_foreach_cb =
+    Option<unsafe extern "C" fn(key: *const c_uchar, len: c_ulong, value: c_int, userdata: *mut c_void) -> c_int>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// An error reported by an export: its return code, and the message kept for
+/// radix_tree_last_error().
+struct Error {
+    code: c_int,
+    message: Cow<'static, str>,
+}
+
+impl Error {
+    fn new(code: c_int, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn set_last_error(function: &str, message: &str) {
+    let message = format!("{}: {}", function, message).replace('\0', "\\0");
+    let message = CString::new(message).expect("NUL bytes were escaped");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Error handler for exports returning a code.
+fn code(code: c_int) -> c_int {
+    code
+}
+
+/// Error handler for exports returning a pointer.
+fn null<T>(_: c_int) -> *mut T {
+    ptr::null_mut()
+}
+
+/// Runs the body of an export, recording its error, and turning panics into
+/// RADIX_TREE_EPANIC, so that they never unwind across the C ABI.
+fn guard<R>(function: &'static str, on_error: fn(c_int) -> R, body: impl FnOnce() -> Result<R, Error>) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => value,
+        Ok(Err(err)) => {
+            set_last_error(function, &err.message);
+            on_error(err.code)
+        }
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown error");
+            set_last_error(function, &format!("internal error: {}", message));
+            on_error(RADIX_TREE_EPANIC)
+        }
+    }
+}
+
+/// Checks the magic number every handle starts with, in debug builds.
+#[inline]
+unsafe fn check_magic<T>(handle: *const T, expected: u32, what: &'static str) -> Result<(), Error> {
+    if cfg!(debug_assertions) && *(handle as *const u32) != expected {
+        return Err(Error::new(RADIX_TREE_EBADHANDLE, what));
+    }
+    Ok(())
+}
+
+/// Number of destroyed handles debug builds keep allocated.
+pub(crate) const QUARANTINE_LEN: usize = 1024;
+
+/// A destroyed handle kept allocated, with the function freeing it.
+struct Quarantined(*mut c_void, unsafe fn(*mut c_void));
+
+// SAFETY: a quarantined handle is no longer used, only freed once evicted.
+unsafe impl Send for Quarantined {}
+
+static QUARANTINE: Mutex<VecDeque<Quarantined>> = Mutex::new(VecDeque::new());
+
+/// Frees a destroyed handle, whose magic number was poisoned.
+///
+/// Debug builds first keep it among the last QUARANTINE_LEN destroyed handles, so that
+/// its magic number can still be checked.
+unsafe fn dispose(handle: *mut c_void, free: unsafe fn(*mut c_void)) {
+    if !cfg!(debug_assertions) {
+        return free(handle);
+    }
+    let evicted = {
+        let mut quarantine = QUARANTINE.lock().unwrap_or_else(|e| e.into_inner());
+        quarantine.push_back(Quarantined(handle, free));
+        if quarantine.len() > QUARANTINE_LEN {
+            quarantine.pop_front()
+        } else {
+            None
+        }
+    };
+    if let Some(Quarantined(handle, free)) = evicted {
+        free(handle);
+    }
+}
+
+unsafe fn free_tree(tree: *mut c_void) {
+    drop(Box::from_raw(tree as *mut radix_tree_t));
+}
+
+unsafe fn free_iter(it: *mut c_void) {
+    libc::free(it);
+}
+
+/// Drops one reference to a tree handle, disposing of it with the last one.
+unsafe fn release_tree(tree: *mut radix_tree_t) {
+    if (*tree).refs.fetch_sub(1, Ordering::AcqRel) == 1 {
+        dispose(tree as *mut c_void, free_tree);
+    }
+}
+
+/// Returns the rax of a live tree handle.
+unsafe fn tree_rax(t: *mut radix_tree_t) -> Result<*mut c_void, Error> {
+    if t.is_null() {
+        return Err(Error::new(-1, "tree is NULL"));
+    }
+    check_magic(t, TREE_MAGIC, "not a live tree handle")?;
+    if (*t).destroyed.load(Ordering::Acquire) {
+        return Err(Error::new(-1, "tree was destroyed"));
+    }
+    Ok((*t).rax)
+}
+
+/// Returns the rax iterator of a usable iterator handle. Fails with -1 if the iterator
+/// was stopped or its tree destroyed.
+unsafe fn iter_raw(it: *mut radix_iter_t) -> Result<*mut RaxIterator, Error> {
+    if it.is_null() {
+        return Err(Error::new(-1, "iterator is NULL"));
+    }
+    check_magic(it, ITER_MAGIC, "not a live iterator handle")?;
+    if (*it).stopped {
+        return Err(Error::new(-1, "iterator was stopped"));
+    }
+    if (*(*it).tree).destroyed.load(Ordering::Acquire) {
+        return Err(Error::new(-1, "tree of the iterator was destroyed"));
+    }
+    Ok(&mut (*it).raw)
+}
+
+/// A rax iterator owned by a single export call, stopped when dropped.
+struct ScopedIter(Box<MaybeUninit<RaxIterator>>);
+
+impl ScopedIter {
+    unsafe fn new(rax: *mut c_void) -> Self {
+        let mut raw = Box::new(MaybeUninit::uninit());
+        raxStart(raw.as_mut_ptr(), rax as *mut Rax);
+        Self(raw)
+    }
+
+    fn as_ptr(&mut self) -> *mut RaxIterator {
+        self.0.as_mut_ptr()
+    }
+}
+
+impl Drop for ScopedIter {
+    fn drop(&mut self) {
+        unsafe { raxStop(self.as_ptr()) };
+    }
+}
+
+/// Validates a key buffer: NULL is only accepted for an empty key.
+fn key_ptr(buf: *const c_uchar, len: c_ulong) -> Result<*const c_uchar, Error> {
+    match (buf.is_null(), len) {
+        (false, _) => Ok(buf),
+        (true, 0) => Ok([].as_ptr()),
+        (true, len) => Err(Error::new(RADIX_TREE_EINVAL, format!("buf is NULL but len is {}", len))),
+    }
+}
+
+/// Validates an array argument: NULL is only accepted for an empty array.
+unsafe fn array<'a, T>(ptr: *const T, n: c_ulong, name: &str) -> Result<&'a [T], Error> {
+    if ptr.is_null() {
+        if n == 0 {
+            return Ok(&[]);
+        }
+        return Err(Error::new(
+            RADIX_TREE_EINVAL,
+            format!("{} is NULL but n is {}", name, n),
+        ));
+    }
+    Ok(std::slice::from_raw_parts(ptr, n as usize))
+}
+
+/// Validates the length of an array argument, so that counts fit the return value.
+fn batch_len(n: c_ulong) -> Result<c_ulong, Error> {
+    if n > c_int::MAX as c_ulong {
+        return Err(Error::new(RADIX_TREE_EINVAL, format!("n is too large ({})", n)));
+    }
+    Ok(n)
+}
+
+/// Returns the ABI version of the library.
+///
+/// # Returns
+///
+/// Returns the RADIX_TREE_ABI_VERSION the library was built with.
+///
+/// # Safety
+///
+/// Always safe to call.
+#[no_mangle]
+pub extern "C" fn radix_tree_abi_version() -> c_int {
+    RADIX_TREE_ABI_VERSION
+}
+
+/// Returns the version of the library.
+///
+/// # Returns
+///
+/// Returns a static NUL-terminated string, such as "0.1.0".
+///
+/// # Safety
+///
+/// The string is owned by the library and must not be freed.
+#[no_mangle]
+pub extern "C" fn radix_tree_version_string() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// Returns the optional capabilities of the library.
+///
+/// # Returns
+///
+/// Returns a bitmask of RADIX_TREE_FEATURE_* flags. Unknown bits must be ignored.
+///
+/// # Safety
+///
+/// Always safe to call.
+#[no_mangle]
+pub extern "C" fn radix_tree_features() -> c_ulong {
+    let features = [
+        (cfg!(debug_assertions), RADIX_TREE_FEATURE_HANDLE_CHECKS),
+        (cfg!(feature = "serde"), RADIX_TREE_FEATURE_SERDE),
+        (cfg!(feature = "json"), RADIX_TREE_FEATURE_JSON),
+        (cfg!(feature = "yaml"), RADIX_TREE_FEATURE_YAML),
+        (cfg!(feature = "toml"), RADIX_TREE_FEATURE_TOML),
+        (cfg!(feature = "idna"), RADIX_TREE_FEATURE_IDNA),
+    ];
+    features
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .fold(0, |mask, (_, flag)| mask | flag)
+}
+
+/// Returns the message of the last error that occurred on the calling thread.
+///
+/// # Returns
+///
+/// Returns a NUL-terminated message, or NULL if no error occurred on this thread yet.
+///
+/// # Safety
+///
+/// The string is owned by the library: it must not be freed, and is only valid until the
+/// next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn radix_tree_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
 
 /// Creates a new radix tree.
 ///
@@ -16,8 +397,21 @@ use libc::{c_int, c_uchar, c_ulong, c_void};
 ///
 /// The returned pointer must be freed with radix_tree_destroy().
 #[no_mangle]
-pub extern "C" fn radix_tree_new() -> *mut c_void {
-    unsafe { tree_new_raw() }
+pub extern "C" fn radix_tree_new() -> *mut radix_tree_t {
+    guard("radix_tree_new", null, || unsafe {
+        let mut tree = Box::new(radix_tree_t {
+            magic: TREE_MAGIC,
+            rax: ptr::null_mut(),
+            refs: AtomicUsize::new(1),
+            destroyed: AtomicBool::new(false),
+            allocated: AtomicUsize::new(0),
+        });
+        tree.rax = charge_to(&tree.allocated, || tree_new_raw());
+        if tree.rax.is_null() {
+            return Err(Error::new(-1, "failed to allocate tree"));
+        }
+        Ok(Box::into_raw(tree))
+    })
 }
 
 /// Destroys a radix tree and frees all associated memory.
@@ -28,15 +422,31 @@ pub extern "C" fn radix_tree_new() -> *mut c_void {
 ///
 /// # Returns
 ///
-/// Returns 0 on success.
+/// Returns 0 on success, RADIX_TREE_EBADHANDLE if t is not a live tree.
 ///
 /// # Safety
 ///
 /// The pointer must have been returned by radix_tree_new().
 /// After calling this function, the pointer is invalid and must not be used.
+/// Iterators created on the tree stay valid handles: they fail on use, and must
+/// still be released with radix_tree_free_it().
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_destroy(t: *mut c_void) -> c_int {
-    unsafe { tree_destroy_raw(t) }
+pub extern "C" fn radix_tree_destroy(t: *mut radix_tree_t) -> c_int {
+    guard("radix_tree_destroy", code, || unsafe {
+        if t.is_null() {
+            return Ok(0);
+        }
+        check_magic(t, TREE_MAGIC, "not a live tree handle")?;
+        if (*t).destroyed.swap(true, Ordering::AcqRel) {
+            return Ok(0);
+        }
+        tree_destroy_raw((*t).rax);
+        (*t).rax = ptr::null_mut();
+        (*t).magic = DEAD_MAGIC;
+        release_tree(t);
+        Ok(0)
+    })
 }
 
 /// Inserts a key-value pair into the tree.
@@ -54,10 +464,17 @@ pub extern "C" fn radix_tree_destroy(t: *mut c_void) -> c_int {
 ///
 /// # Safety
 ///
-/// t must be a valid tree pointer, buf must point to at least len bytes.
+/// t must be a valid tree pointer, buf must point to at least len bytes (or be NULL if len is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_insert(t: *mut c_void, buf: *const c_uchar, len: c_ulong, idx: c_int) -> c_int {
-    unsafe { tree_insert_raw(t, buf as *const u8, len as usize, idx) }
+pub extern "C" fn radix_tree_insert(t: *mut radix_tree_t, buf: *const c_uchar, len: c_ulong, idx: c_int) -> c_int {
+    guard("radix_tree_insert", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let buf = key_ptr(buf, len)?;
+        Ok(charge_to(&(*t).allocated, || {
+            tree_insert_raw(rax, buf, len as usize, idx)
+        }))
+    })
 }
 
 /// Finds an exact match_engine for a key.
@@ -70,14 +487,19 @@ pub extern "C" fn radix_tree_insert(t: *mut c_void, buf: *const c_uchar, len: c_
 ///
 /// # Returns
 ///
-/// Returns the associated value cast to a pointer, or NULL if not found.
+/// Returns the associated value cast to a pointer, or NULL if not found or on error.
 ///
 /// # Safety
 ///
-/// t must be a valid tree pointer, buf must point to at least len bytes.
+/// t must be a valid tree pointer, buf must point to at least len bytes (or be NULL if len is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_find(t: *mut c_void, buf: *const c_uchar, len: c_ulong) -> *mut c_void {
-    unsafe { tree_find_raw(t, buf as *const u8, len as usize) }
+pub extern "C" fn radix_tree_find(t: *mut radix_tree_t, buf: *const c_uchar, len: c_ulong) -> *mut c_void {
+    guard("radix_tree_find", null, || unsafe {
+        let rax = tree_rax(t)?;
+        let buf = key_ptr(buf, len)?;
+        Ok(tree_find_raw(rax, buf, len as usize))
+    })
 }
 
 /// Removes a key from the tree.
@@ -94,10 +516,42 @@ pub extern "C" fn radix_tree_find(t: *mut c_void, buf: *const c_uchar, len: c_ul
 ///
 /// # Safety
 ///
-/// t must be a valid tree pointer, buf must point to at least len bytes.
+/// t must be a valid tree pointer, buf must point to at least len bytes (or be NULL if len is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn radix_tree_remove(t: *mut radix_tree_t, buf: *const c_uchar, len: c_ulong) -> c_int {
+    guard("radix_tree_remove", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let buf = key_ptr(buf, len)?;
+        Ok(charge_to(&(*t).allocated, || tree_remove_raw(rax, buf, len as usize)))
+    })
+}
+
+/// Returns the memory used by the nodes of a tree.
+///
+/// # Arguments
+///
+/// * t - Pointer to the tree
+/// * bytes - Receives the number of bytes currently allocated for the tree
+///
+/// # Returns
+///
+/// Returns 0 on success, negative on error.
+///
+/// # Safety
+///
+/// t must be a valid tree pointer and bytes must point to writable memory.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_remove(t: *mut c_void, buf: *const c_uchar, len: c_ulong) -> c_int {
-    unsafe { tree_remove_raw(t, buf as *const u8, len as usize) }
+pub extern "C" fn radix_tree_allocated_bytes(t: *mut radix_tree_t, bytes: *mut c_ulong) -> c_int {
+    guard("radix_tree_allocated_bytes", code, || unsafe {
+        tree_rax(t)?;
+        if bytes.is_null() {
+            return Err(Error::new(RADIX_TREE_EINVAL, "bytes is NULL"));
+        }
+        *bytes = (*t).allocated.load(Ordering::Relaxed) as c_ulong;
+        Ok(0)
+    })
 }
 
 /// Creates a new iterator for the tree.
@@ -113,10 +567,23 @@ pub extern "C" fn radix_tree_remove(t: *mut c_void, buf: *const c_uchar, len: c_
 /// # Safety
 ///
 /// t must be a valid tree pointer.
-/// The returned iterator must be freed with libc::free() after calling radix_tree_stop().
+/// The returned iterator must be released with radix_tree_free_it().
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_new_it(t: *mut c_void) -> *mut c_void {
-    unsafe { tree_new_it_raw(t) }
+pub extern "C" fn radix_tree_new_it(t: *mut radix_tree_t) -> *mut radix_iter_t {
+    guard("radix_tree_new_it", null, || unsafe {
+        let rax = tree_rax(t)?;
+        let it = libc::malloc(std::mem::size_of::<radix_iter_t>()) as *mut radix_iter_t;
+        if it.is_null() {
+            return Err(Error::new(-1, "failed to allocate iterator"));
+        }
+        raxStart(ptr::addr_of_mut!((*it).raw), rax as *mut Rax);
+        ptr::addr_of_mut!((*it).magic).write(ITER_MAGIC);
+        ptr::addr_of_mut!((*it).tree).write(t);
+        ptr::addr_of_mut!((*it).stopped).write(false);
+        (*t).refs.fetch_add(1, Ordering::AcqRel);
+        Ok(it)
+    })
 }
 
 /// Initializes an iterator for prefix searching.
@@ -130,19 +597,30 @@ pub extern "C" fn radix_tree_new_it(t: *mut c_void) -> *mut c_void {
 ///
 /// # Returns
 ///
-/// Returns the iterator pointer on success, NULL on failure.
+/// Returns the iterator pointer on success, NULL on failure, including when it was not
+/// created on tree.
 ///
 /// # Safety
 ///
-/// All pointers must be valid, buf must point to at least len bytes.
+/// All pointers must be valid, buf must point to at least len bytes (or be NULL if len is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn radix_tree_search(
-    tree: *mut c_void,
-    it: *mut c_void,
+    tree: *mut radix_tree_t,
+    it: *mut radix_iter_t,
     buf: *const c_uchar,
     len: c_ulong,
-) -> *mut c_void {
-    unsafe { tree_search_raw(tree, it, buf as *const u8, len as usize) }
+) -> *mut radix_iter_t {
+    guard("radix_tree_search", null, || unsafe {
+        let rax = tree_rax(tree)?;
+        let iter = iter_raw(it)?;
+        if (*it).tree != tree {
+            return Err(Error::new(RADIX_TREE_EINVAL, "iterator was created on another tree"));
+        }
+        let buf = key_ptr(buf, len)?;
+        tree_search_raw(rax, iter as *mut c_void, buf, len as usize);
+        Ok(it)
+    })
 }
 
 /// Moves to the previous matching prefix.
@@ -155,32 +633,33 @@ pub extern "C" fn radix_tree_search(
 ///
 /// # Returns
 ///
-/// Returns the associated value on success, -1 if no more matches.
+/// Returns the associated value on success, -1 if no more matches or if the iterator
+/// was stopped, or another negative error code.
 ///
 /// # Safety
 ///
-/// it must be a valid iterator, buf must point to at least len bytes.
+/// it must be a valid iterator, buf must point to at least len bytes (or be NULL if len is 0).
 #[no_mangle]
-pub extern "C" fn radix_tree_prev(it: *mut c_void, buf: *const c_uchar, len: c_ulong) -> c_int {
-    if it.is_null() || buf.is_null() {
-        return -1;
-    }
-    let iter_ptr = it as *mut RaxIterator;
-    loop {
-        let res = unsafe { crate::ffi::raxPrev(iter_ptr) };
-        if res == 0 {
-            return -1;
-        }
-        let key_len = unsafe { (*iter_ptr).key_len };
-        if key_len > len as usize {
-            continue;
+pub extern "C" fn radix_tree_prev(it: *mut radix_iter_t, buf: *const c_uchar, len: c_ulong) -> c_int {
+    guard("radix_tree_prev", code, || unsafe {
+        let iter_ptr = iter_raw(it)?;
+        let buf = key_ptr(buf, len)?;
+        loop {
+            let res = crate::ffi::raxPrev(iter_ptr);
+            if res == 0 {
+                return Ok(-1);
+            }
+            let key_len = (*iter_ptr).key_len;
+            if key_len > len as usize {
+                continue;
+            }
+            let cmp = libc::memcmp(buf as *const c_void, (*iter_ptr).key as *const c_void, key_len);
+            if cmp != 0 {
+                continue;
+            }
+            return Ok((*iter_ptr).data as isize as c_int);
         }
-        let cmp = unsafe { libc::memcmp(buf as *const c_void, (*iter_ptr).key as *const c_void, key_len) };
-        if cmp != 0 {
-            continue;
-        }
-        return unsafe { (*iter_ptr).data as isize as c_int };
-    }
+    })
 }
 
 /// Moves to the next matching key.
@@ -193,30 +672,31 @@ pub extern "C" fn radix_tree_prev(it: *mut c_void, buf: *const c_uchar, len: c_u
 ///
 /// # Returns
 ///
-/// Returns the associated value on success, -1 if no more matches.
+/// Returns the associated value on success, -1 if no more matches or if the iterator
+/// was stopped, or another negative error code.
 ///
 /// # Safety
 ///
-/// it must be a valid iterator, buf must point to at least len bytes.
+/// it must be a valid iterator, buf must point to at least len bytes (or be NULL if len is 0).
 #[no_mangle]
-pub extern "C" fn radix_tree_next(it: *mut c_void, buf: *const c_uchar, len: c_ulong) -> c_int {
-    if it.is_null() || buf.is_null() {
-        return -1;
-    }
-    let iter_ptr = it as *mut RaxIterator;
-    let res = unsafe { crate::ffi::raxNext(iter_ptr) };
-    if res == 0 {
-        return -1;
-    }
-    let key_len = unsafe { (*iter_ptr).key_len };
-    if key_len > len as usize {
-        return -1;
-    }
-    let cmp = unsafe { libc::memcmp(buf as *const c_void, (*iter_ptr).key as *const c_void, key_len) };
-    if cmp != 0 {
-        return -1;
-    }
-    unsafe { (*iter_ptr).data as isize as c_int }
+pub extern "C" fn radix_tree_next(it: *mut radix_iter_t, buf: *const c_uchar, len: c_ulong) -> c_int {
+    guard("radix_tree_next", code, || unsafe {
+        let iter_ptr = iter_raw(it)?;
+        let buf = key_ptr(buf, len)?;
+        let res = crate::ffi::raxNext(iter_ptr);
+        if res == 0 {
+            return Ok(-1);
+        }
+        let key_len = (*iter_ptr).key_len;
+        if key_len > len as usize {
+            return Ok(-1);
+        }
+        let cmp = libc::memcmp(buf as *const c_void, (*iter_ptr).key as *const c_void, key_len);
+        if cmp != 0 {
+            return Ok(-1);
+        }
+        Ok((*iter_ptr).data as isize as c_int)
+    })
 }
 
 /// Moves iterator up to find the next shorter prefix match_engine.
@@ -229,15 +709,21 @@ pub extern "C" fn radix_tree_next(it: *mut c_void, buf: *const c_uchar, len: c_u
 ///
 /// # Returns
 ///
-/// Returns the associated value on success, -1 if no more matches.
+/// Returns the associated value on success, -1 if no more matches or if the iterator
+/// was stopped, or another negative error code.
 ///
 /// # Safety
 ///
 /// it must be a valid iterator initialized with radix_tree_search(),
-/// buf must point to at least len bytes.
+/// buf must point to at least len bytes (or be NULL if len is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_up(it: *mut c_void, buf: *const c_uchar, len: c_ulong) -> c_int {
-    unsafe { tree_up_raw(it, buf as *const u8, len as usize) }
+pub extern "C" fn radix_tree_up(it: *mut radix_iter_t, buf: *const c_uchar, len: c_ulong) -> c_int {
+    guard("radix_tree_up", code, || unsafe {
+        let iter_ptr = iter_raw(it)?;
+        let buf = key_ptr(buf, len)?;
+        Ok(tree_up_raw(iter_ptr as *mut c_void, buf, len as usize))
+    })
 }
 
 /// Stops an iterator and releases its internal resources.
@@ -248,14 +734,290 @@ pub extern "C" fn radix_tree_up(it: *mut c_void, buf: *const c_uchar, len: c_ulo
 ///
 /// # Returns
 ///
-/// Returns 0 on success.
+/// Returns 0 on success, RADIX_TREE_EBADHANDLE if it is not an iterator.
 ///
 /// # Safety
 ///
 /// it must be a valid iterator.
-/// After calling this, the iterator memory must still be freed with libc::free().
+/// After calling this, the iterator can no longer be used, and its memory must still be
+/// freed with free(). Prefer radix_tree_free_it(), which does both.
+#[no_mangle]
+pub extern "C" fn radix_tree_stop(it: *mut radix_iter_t) -> c_int {
+    guard("radix_tree_stop", code, || unsafe {
+        if it.is_null() {
+            return Ok(0);
+        }
+        check_magic(it, ITER_MAGIC, "not a live iterator handle")?;
+        if (*it).stopped {
+            return Ok(0);
+        }
+        tree_stop_raw(ptr::addr_of_mut!((*it).raw) as *mut c_void);
+        (*it).stopped = true;
+        release_tree((*it).tree);
+        Ok(0)
+    })
+}
+
+/// Stops an iterator and frees it.
+///
+/// # Arguments
+///
+/// * it - Pointer to the iterator, may be NULL
+///
+/// # Returns
+///
+/// Returns 0 on success, RADIX_TREE_EBADHANDLE if it is not an iterator.
+///
+/// # Safety
+///
+/// it must be NULL or an iterator returned by radix_tree_new_it() that was not freed yet.
+/// It may be called after the tree was destroyed. After calling this function, the
+/// pointer is invalid and must not be used.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
-pub extern "C" fn radix_tree_stop(it: *mut c_void) -> c_int {
-    unsafe { tree_stop_raw(it) }
+pub extern "C" fn radix_tree_free_it(it: *mut radix_iter_t) -> c_int {
+    guard("radix_tree_free_it", code, || unsafe {
+        if it.is_null() {
+            return Ok(0);
+        }
+        check_magic(it, ITER_MAGIC, "not a live iterator handle")?;
+        if !(*it).stopped {
+            tree_stop_raw(ptr::addr_of_mut!((*it).raw) as *mut c_void);
+            release_tree((*it).tree);
+        }
+        (*it).magic = DEAD_MAGIC;
+        dispose(it as *mut c_void, free_iter);
+        Ok(0)
+    })
 }
 
+/// Invokes a callback for every key that is a prefix of a path, from the longest to the
+/// shortest, in a single call.
+///
+/// # Arguments
+///
+/// * t - Pointer to the tree
+/// * buf - Pointer to the path
+/// * len - Length of the path in bytes
+/// * cb - Callback invoked for each matching key, returning non-zero to stop
+/// * userdata - Passed unchanged to the callback
+///
+/// # Returns
+///
+/// Returns the number of callback invocations, or a negative error code.
+///
+/// # Safety
+///
+/// t must be a valid tree pointer, buf must point to at least len bytes (or be NULL if
+/// len is 0). The callback must not modify the tree.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn radix_tree_foreach_prefix(
+    t: *mut radix_tree_t,
+    buf: *const c_uchar,
+    len: c_ulong,
+    cb: radix_tree_foreach_cb,
+    userdata: *mut c_void,
+) -> c_int {
+    guard("radix_tree_foreach_prefix", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let buf = key_ptr(buf, len)?;
+        let cb = cb.ok_or_else(|| Error::new(RADIX_TREE_EINVAL, "cb is NULL"))?;
+        let mut it = ScopedIter::new(rax);
+        let iter = it.as_ptr();
+        tree_search_raw(rax, iter as *mut c_void, buf, len as usize);
+        let mut count = 0;
+        while raxUp(iter) != 0 {
+            let key_len = (*iter).key_len;
+            if key_len > len as usize || libc::memcmp(buf as *const c_void, (*iter).key as *const c_void, key_len) != 0
+            {
+                continue;
+            }
+            count += 1;
+            if cb(
+                (*iter).key,
+                key_len as c_ulong,
+                (*iter).data as isize as c_int,
+                userdata,
+            ) != 0
+            {
+                break;
+            }
+        }
+        Ok(count)
+    })
+}
+
+/// Invokes a callback for every key starting with a prefix, in lexicographic order, in a
+/// single call.
+///
+/// # Arguments
+///
+/// * t - Pointer to the tree
+/// * prefix - Pointer to the prefix
+/// * len - Length of the prefix in bytes, 0 to visit every key
+/// * cb - Callback invoked for each matching key, returning non-zero to stop
+/// * userdata - Passed unchanged to the callback
+///
+/// # Returns
+///
+/// Returns the number of callback invocations, or a negative error code.
+///
+/// # Safety
+///
+/// t must be a valid tree pointer, prefix must point to at least len bytes (or be NULL
+/// if len is 0). The callback must not modify the tree.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn radix_tree_foreach(
+    t: *mut radix_tree_t,
+    prefix: *const c_uchar,
+    len: c_ulong,
+    cb: radix_tree_foreach_cb,
+    userdata: *mut c_void,
+) -> c_int {
+    static OP_GE: [c_uchar; 3] = [b'>', b'=', 0];
+
+    guard("radix_tree_foreach", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let prefix = key_ptr(prefix, len)?;
+        let cb = cb.ok_or_else(|| Error::new(RADIX_TREE_EINVAL, "cb is NULL"))?;
+        let mut it = ScopedIter::new(rax);
+        let iter = it.as_ptr();
+        raxSeek(iter, OP_GE.as_ptr(), prefix, len);
+        let mut count = 0;
+        while raxNext(iter) != 0 {
+            let key_len = (*iter).key_len;
+            if key_len < len as usize
+                || libc::memcmp(prefix as *const c_void, (*iter).key as *const c_void, len as usize) != 0
+            {
+                break;
+            }
+            count += 1;
+            if cb(
+                (*iter).key,
+                key_len as c_ulong,
+                (*iter).data as isize as c_int,
+                userdata,
+            ) != 0
+            {
+                break;
+            }
+        }
+        Ok(count)
+    })
+}
+
+/// Inserts several key-value pairs in a single call.
+///
+/// # Arguments
+///
+/// * t - Pointer to the tree
+/// * keys - Array of n pointers to key data
+/// * lens - Array of n key lengths in bytes
+/// * idxs - Array of n values to associate with the keys
+/// * n - Number of keys
+///
+/// # Returns
+///
+/// Returns the number of keys that were not in the tree yet (existing keys are updated),
+/// or a negative error code. Arguments are validated before any insertion.
+///
+/// # Safety
+///
+/// t must be a valid tree pointer, each array must hold n elements (or be NULL if n is
+/// 0), and keys[i] must point to at least lens[i] bytes (or be NULL if lens[i] is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn radix_tree_insert_many(
+    t: *mut radix_tree_t,
+    keys: *const *const c_uchar,
+    lens: *const c_ulong,
+    idxs: *const c_int,
+    n: c_ulong,
+) -> c_int {
+    guard("radix_tree_insert_many", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let n = batch_len(n)?;
+        let (keys, lens, idxs) = (
+            array(keys, n, "keys")?,
+            array(lens, n, "lens")?,
+            array(idxs, n, "idxs")?,
+        );
+        let keys = keys
+            .iter()
+            .zip(lens)
+            .enumerate()
+            .map(|(i, (&key, &len))| {
+                key_ptr(key, len).map_err(|_| {
+                    Error::new(
+                        RADIX_TREE_EINVAL,
+                        format!("keys[{}] is NULL but lens[{}] is {}", i, i, len),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut added = 0;
+        for ((key, &len), &idx) in keys.into_iter().zip(lens).zip(idxs) {
+            let rc = charge_to(&(*t).allocated, || tree_insert_raw(rax, key, len as usize, idx));
+            if rc < 0 {
+                return Err(Error::new(rc, "insertion failed"));
+            }
+            added += rc;
+        }
+        Ok(added)
+    })
+}
+
+/// Finds the longest prefix match of several paths in a single call.
+///
+/// # Arguments
+///
+/// * t - Pointer to the tree
+/// * paths - Array of n pointers to path data
+/// * lens - Array of n path lengths in bytes
+/// * n - Number of paths
+/// * results - Array of n values, receiving the value of the longest prefix of each
+///   path, or -1 if none
+///
+/// # Returns
+///
+/// Returns the number of paths that matched, or a negative error code.
+///
+/// # Safety
+///
+/// t must be a valid tree pointer, each array must hold n elements (or be NULL if n is
+/// 0), and paths[i] must point to at least lens[i] bytes (or be NULL if lens[i] is 0).
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
+#[no_mangle]
+pub extern "C" fn radix_tree_match_many(
+    t: *mut radix_tree_t,
+    paths: *const *const c_uchar,
+    lens: *const c_ulong,
+    n: c_ulong,
+    results: *mut c_int,
+) -> c_int {
+    guard("radix_tree_match_many", code, || unsafe {
+        let rax = tree_rax(t)?;
+        let n = batch_len(n)?;
+        let (paths, lens) = (array(paths, n, "paths")?, array(lens, n, "lens")?);
+        if results.is_null() && n > 0 {
+            return Err(Error::new(RADIX_TREE_EINVAL, format!("results is NULL but n is {}", n)));
+        }
+        let mut it = ScopedIter::new(rax);
+        let iter = it.as_ptr() as *mut c_void;
+        let mut matched = 0;
+        for (i, (&path, &len)) in paths.iter().zip(lens).enumerate() {
+            let path = key_ptr(path, len).map_err(|_| {
+                Error::new(
+                    RADIX_TREE_EINVAL,
+                    format!("paths[{}] is NULL but lens[{}] is {}", i, i, len),
+                )
+            })?;
+            let value = tree_longest_prefix_raw(rax, iter, path, len as usize);
+            *results.add(i) = if value > 0 { value } else { -1 };
+            matched += (value > 0) as c_int;
+        }
+        Ok(matched)
+    })
+}
@@ -0,0 +1,249 @@
+//! Prefix completion on the Radix Tree
+//!
+//! Completions are the keys below a prefix, either in lexicographic order, which is a
+//! plain depth-first walk stopping after `k` keys, or by decreasing weight. Weights are
+//! kept next to the tree, and the maximum weight below every node is cached, so that a
+//! best-first walk only descends into the subtrees that can still hold one of the top `k`
+//! keys. The cache is built on the first weighted query after a modification.
+
+use crate::node::{Cursor, Node};
+use crate::radix_tree::RadixTree;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::OnceLock;
+
+/// Per-key weights of a tree, with the cached maximum weight below each node.
+#[derive(Default)]
+pub(crate) struct Weights {
+    by_key: HashMap<Box<[u8]>, u64>,
+    /// Maximum weight of the keys below each node, by node id. Nodes with only keys of
+    /// weight 0 are left out.
+    max: OnceLock<HashMap<usize, u64>>,
+}
+
+impl Weights {
+    /// Drops the cached maximum weights, which refer to nodes that a modification of the
+    /// tree may have moved.
+    pub(crate) fn invalidate(&mut self) {
+        self.max.take();
+    }
+
+    /// Forgets the weight of a removed key.
+    pub(crate) fn remove(&mut self, key: &[u8]) {
+        self.by_key.remove(key);
+        self.invalidate();
+    }
+
+    fn get(&self, key: &[u8]) -> u64 {
+        self.by_key.get(key).copied().unwrap_or(0)
+    }
+
+    /// Returns the maximum weight below every node, walking the path of each weighted key.
+    fn max(&self, tree: &RadixTree) -> &HashMap<usize, u64> {
+        self.max.get_or_init(|| {
+            let mut max = HashMap::new();
+            for (key, &weight) in &self.by_key {
+                let mut cursor = Some(Cursor::new(tree));
+                let mut rest = key.iter();
+                while let Some(current) = cursor {
+                    if let Some(node) = current.node() {
+                        let entry = max.entry(node.id()).or_insert(0);
+                        *entry = weight.max(*entry);
+                    }
+                    cursor = rest.next().and_then(|&byte| current.step(byte));
+                }
+            }
+            max
+        })
+    }
+}
+
+/// A pending item of the best-first walk: a key to return, or a subtree to expand.
+struct Candidate<'a> {
+    /// The weight of the key, or the maximum weight in the subtree.
+    weight: u64,
+    key: Vec<u8>,
+    item: Item<'a>,
+}
+
+enum Item<'a> {
+    Key(i32),
+    Subtree(Node<'a>),
+}
+
+impl Candidate<'_> {
+    /// Candidates pop by decreasing weight then increasing key, and a key before the subtree
+    /// starting with it: the keys of a subtree are never less than its prefix.
+    fn rank(&self) -> (u64, Reverse<&[u8]>, bool) {
+        (self.weight, Reverse(&self.key), matches!(self.item, Item::Key(_)))
+    }
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// Returns the children of a node with their keys, in order.
+fn children<'a>(node: Node<'a>, key: &[u8]) -> Vec<(Node<'a>, Vec<u8>)> {
+    let chars = node.chars();
+    (0..node.child_count())
+        .map(|i| {
+            let mut child_key = key.to_vec();
+            if node.is_compressed() {
+                child_key.extend_from_slice(chars);
+            } else {
+                child_key.push(chars[i]);
+            }
+            (node.child(i), child_key)
+        })
+        .collect()
+}
+
+impl RadixTree {
+    /// Returns the first node holding keys starting with `prefix`, with its key.
+    fn subtree(&self, prefix: &[u8]) -> Option<(Node<'_>, Vec<u8>)> {
+        let prefix = &*self.fold(prefix);
+        let (node, rest) = Cursor::new(self).walk(prefix)?.descend();
+        Some((node, [prefix, rest].concat()))
+    }
+
+    /// Returns up to `k` keys starting with `prefix` with their values, in lexicographic
+    /// order.
+    ///
+    /// The walk stops after `k` keys, whatever the number of keys under the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api/users", 1), ("/api/posts", 2), ("/api/plugins", 3), ("/admin", 4)])
+    ///     .unwrap();
+    ///
+    /// let completions = tree.complete("/api/p", 10);
+    /// assert_eq!(completions, vec![(b"/api/plugins".to_vec(), 3), (b"/api/posts".to_vec(), 2)]);
+    /// assert_eq!(tree.complete("/a", 1), vec![(b"/admin".to_vec(), 4)]);
+    /// ```
+    pub fn complete<K: AsRef<[u8]>>(&self, prefix: K, k: usize) -> Vec<(Vec<u8>, i32)> {
+        let mut completions = Vec::new();
+        let Some(start) = self.subtree(prefix.as_ref()) else {
+            return completions;
+        };
+        let mut stack = vec![start];
+        while let Some((node, key)) = stack.pop() {
+            if completions.len() == k {
+                break;
+            }
+            stack.extend(children(node, &key).into_iter().rev());
+            if let Some(data) = node.data() {
+                completions.push((self.spelling(key), data as isize as i32));
+            }
+        }
+        completions
+    }
+
+    /// Returns up to `k` keys starting with `prefix` with their values, by decreasing
+    /// weight, then in lexicographic order.
+    ///
+    /// Keys have a weight of 0 until [`set_weight`](Self::set_weight) is called. The
+    /// maximum weight below every node is cached, so only the subtrees that can hold one of
+    /// the top `k` keys are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api/users", 1), ("/api/posts", 2), ("/api/plugins", 3)]).unwrap();
+    /// tree.set_weight("/api/posts", 50);
+    /// tree.set_weight("/api/users", 80);
+    ///
+    /// let top: Vec<_> = tree.complete_by_weight("/api/", 2).into_iter().map(|(_, v)| v).collect();
+    /// assert_eq!(top, vec![1, 2]);
+    /// ```
+    pub fn complete_by_weight<K: AsRef<[u8]>>(&self, prefix: K, k: usize) -> Vec<(Vec<u8>, i32)> {
+        let mut completions = Vec::new();
+        let Some((node, key)) = self.subtree(prefix.as_ref()) else {
+            return completions;
+        };
+        let max = self.weights.max(self);
+        let bound = |node: &Node<'_>| max.get(&node.id()).copied().unwrap_or(0);
+        let mut heap = BinaryHeap::from([Candidate {
+            weight: bound(&node),
+            key,
+            item: Item::Subtree(node),
+        }]);
+        while completions.len() < k {
+            let Some(candidate) = heap.pop() else {
+                break;
+            };
+            let node = match candidate.item {
+                Item::Key(value) => {
+                    completions.push((self.spelling(candidate.key), value));
+                    continue;
+                }
+                Item::Subtree(node) => node,
+            };
+            if let Some(data) = node.data() {
+                heap.push(Candidate {
+                    weight: self.weights.get(&candidate.key),
+                    key: candidate.key.clone(),
+                    item: Item::Key(data as isize as i32),
+                });
+            }
+            heap.extend(
+                children(node, &candidate.key)
+                    .into_iter()
+                    .map(|(child, key)| Candidate {
+                        weight: bound(&child),
+                        key,
+                        item: Item::Subtree(child),
+                    }),
+            );
+        }
+        completions
+    }
+
+    /// Sets the weight of a key, used to rank [`complete_by_weight`](Self::complete_by_weight)
+    /// results. Returns `false`, leaving the tree unchanged, if the key is not in the tree.
+    ///
+    /// Weights are kept in memory next to the tree: they are dropped when the key is
+    /// removed, and are not part of snapshots, flattened trees or serialized trees.
+    pub fn set_weight<K: AsRef<[u8]>>(&mut self, key: K, weight: u64) -> bool {
+        let key = self.fold(key.as_ref());
+        if self.find_exact(&key).is_none() {
+            return false;
+        }
+        if weight == 0 {
+            self.weights.by_key.remove(&*key);
+        } else {
+            self.weights.by_key.insert(key.into(), weight);
+        }
+        self.weights.invalidate();
+        true
+    }
+
+    /// Returns the weight of a key, or `None` if the key is not in the tree.
+    pub fn weight<K: AsRef<[u8]>>(&self, key: K) -> Option<u64> {
+        let key = self.fold(key.as_ref());
+        self.find_exact(&key).map(|_| self.weights.get(&key))
+    }
+}
@@ -0,0 +1,259 @@
+//! Declarative route-table loader
+//!
+//! This model parses route tables from JSON, YAML or TOML files (behind the `json`,
+//! `yaml` and `toml` features) and builds a ready [`Router`] from them.
+//!
+//! # Schema
+//!
+//! A route table is a single `routes` list, each entry following [`Route`]:
+//!
+//! ```yaml
+//! routes:
+//!   - id: 1                          # required, > 0, unique
+//!     paths: ["/api/users", "/v1/*"] # required, a trailing `*` matches by prefix
+//!     hosts: ["*.example.com"]       # optional, any host when absent
+//!     methods: ["GET", "POST"]       # optional, any method when absent
+//!     priority: 10                   # optional, defaults to 0
+//! ```
+//!
+//! The equivalent TOML uses `[[routes]]` tables, and JSON an object with a `routes` array.
+//! Unknown fields are rejected.
+//!
+//! # Errors
+//!
+//! Syntax errors carry the line and column reported by the parser. Invalid and duplicate
+//! routes are identified by their index in the `routes` list and their id, without a
+//! location.
+
+use crate::router::{Route, RouteError, Router};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+
+/// A position in a configuration file (1-based).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The category of a [`ConfigError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// The file could not be read.
+    Io,
+    /// The file extension does not match any enabled format.
+    UnsupportedFormat,
+    /// The file is not valid for its format, or does not follow the schema.
+    Syntax,
+    /// A route is invalid (see [`RouteError`]).
+    InvalidRoute,
+    /// A route duplicates the id or a path of a previous route.
+    Duplicate,
+    /// The radix tree of the router failed, e.g. on memory allocation failure.
+    Tree,
+}
+
+/// Error returned when loading a route table.
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    kind: ConfigErrorKind,
+    message: String,
+    location: Option<Location>,
+}
+
+impl ConfigError {
+    pub(crate) fn new(kind: ConfigErrorKind, message: impl Into<String>, location: Option<Location>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            location,
+        }
+    }
+
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ConfigErrorKind {
+        self.kind
+    }
+
+    /// Returns where the error occurred in the source, when known.
+    pub fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    /// Returns the error message, without location.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{}: {}", location, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Supported configuration formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "toml")]
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Guesses the format from a file extension (`json`, `yaml`/`yml`, `toml`).
+    ///
+    /// Returns `None` if the extension is unknown or its feature is disabled.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            #[cfg(feature = "json")]
+            "json" => Some(ConfigFormat::Json),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            #[cfg(feature = "toml")]
+            "toml" => Some(ConfigFormat::Toml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RouteTable {
+    routes: Vec<Route>,
+}
+
+/// Removes the " at line X column Y" suffix that parsers append to their messages,
+/// since the location is reported separately.
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn strip_position(mut message: String) -> String {
+    if let Some(i) = message.rfind(" at line ") {
+        message.truncate(i);
+    }
+    message
+}
+
+/// Returns the line and column of a byte offset in `src`.
+#[cfg(any(feature = "yaml", feature = "toml"))]
+fn locate(src: &str, offset: usize) -> Location {
+    let before = &src[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        line,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// A parsed route table, not yet validated.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "json")]
+/// # {
+/// use radix_route_matcher::{ConfigFormat, RouteConfig};
+///
+/// let src = r#"{ "routes": [ { "id": 1, "paths": ["/api/*"], "methods": ["GET"] } ] }"#;
+/// let router = RouteConfig::parse(src, ConfigFormat::Json).unwrap().build().unwrap();
+///
+/// let iter = router.create_iter().unwrap();
+/// assert_eq!(router.match_route(&iter, "/api/users", None, Some("GET")), Some(1));
+/// # }
+/// ```
+pub struct RouteConfig {
+    routes: Vec<Route>,
+}
+
+impl RouteConfig {
+    /// Parses a route table in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigErrorKind::Syntax`] error, located when the parser reports a
+    /// position, if the source is malformed or does not follow the schema.
+    pub fn parse(source: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        let syntax =
+            |message: String, location: Option<Location>| ConfigError::new(ConfigErrorKind::Syntax, message, location);
+        let table: RouteTable = match format {
+            #[cfg(feature = "json")]
+            ConfigFormat::Json => serde_json::from_str(source).map_err(|e| {
+                let location = (e.line() > 0).then_some(Location {
+                    line: e.line(),
+                    column: e.column(),
+                });
+                syntax(strip_position(e.to_string()), location)
+            })?,
+            #[cfg(feature = "yaml")]
+            ConfigFormat::Yaml => serde_yaml::from_str(source).map_err(|e| {
+                let location = e.location().map(|l| locate(source, l.index()));
+                syntax(strip_position(e.to_string()), location)
+            })?,
+            #[cfg(feature = "toml")]
+            ConfigFormat::Toml => toml::from_str(source).map_err(|e| {
+                let location = e.span().map(|span| locate(source, span.start));
+                syntax(e.message().to_string(), location)
+            })?,
+        };
+        Ok(Self { routes: table.routes })
+    }
+
+    /// Reads and parses a route table, guessing the format from the file extension.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its extension is not supported, or
+    /// it cannot be parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path).ok_or_else(|| {
+            let message = format!("unsupported route config format: {}", path.display());
+            ConfigError::new(ConfigErrorKind::UnsupportedFormat, message, None)
+        })?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::new(ConfigErrorKind::Io, format!("{}: {}", path.display(), e), None))?;
+        Self::parse(&source, format)
+    }
+
+    /// Returns the parsed routes.
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Validates every route and builds a router.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first invalid or duplicate route, identified by its index and id.
+    pub fn build(&self) -> Result<Router, ConfigError> {
+        let mut router = Router::new().map_err(|e| ConfigError::new(ConfigErrorKind::Tree, e, None))?;
+        for (i, route) in self.routes.iter().enumerate() {
+            if let Err(err) = router.add(route.clone()) {
+                let kind = match err {
+                    RouteError::DuplicateId(_) | RouteError::DuplicatePath(_) => ConfigErrorKind::Duplicate,
+                    RouteError::Tree(_) => ConfigErrorKind::Tree,
+                    _ => ConfigErrorKind::InvalidRoute,
+                };
+                let message = format!("routes[{}] (id {}): {}", i, route.id, err);
+                return Err(ConfigError::new(kind, message, None));
+            }
+        }
+        Ok(router)
+    }
+}
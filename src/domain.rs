@@ -0,0 +1,290 @@
+//! Domain name rule sets built on the Radix Tree
+//!
+//! Domain names share their suffixes rather than their prefixes, so this model stores every
+//! name with its labels reversed, each followed by a dot: `www.example.com` becomes
+//! `com.example.www.`. A subdomain-inclusive rule is then a prefix of the keys of all the
+//! names it covers, and exact rules get a terminating NUL byte so that they only match
+//! their own name. The longest matching rule is the most specific one.
+
+use crate::radix_tree::RadixTree;
+use crate::slab::Slab;
+use std::fmt;
+use std::io::BufRead;
+use std::net::IpAddr;
+
+/// Maximum length of a domain name, without its trailing dot.
+const MAX_NAME_LEN: usize = 253;
+/// Maximum length of a label.
+const MAX_LABEL_LEN: usize = 63;
+
+/// Error returned for invalid domain names, or when a [`DomainSet`] cannot be updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainError {
+    /// A name is not a valid domain name.
+    InvalidName(String),
+    /// A line of a list could not be parsed; lines are numbered from 1.
+    InvalidLine { line: usize, content: String },
+    /// Reading a list failed.
+    Io(String),
+    /// The underlying tree rejected the insertion.
+    Tree(i32),
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DomainError::InvalidName(name) => write!(f, "invalid domain name {:?}", name),
+            DomainError::InvalidLine { line, content } => write!(f, "line {}: invalid entry {:?}", line, content),
+            DomainError::Io(message) => write!(f, "failed to read domain list: {}", message),
+            DomainError::Tree(rc) => write!(f, "radix tree insertion failed with error {}", rc),
+        }
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+/// Format of a domain list read by [`DomainSet::load`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    /// `/etc/hosts` style: an IP address followed by one or more names, each loaded as an
+    /// exact rule. Names that are IP addresses (as in `0.0.0.0 0.0.0.0`) are skipped.
+    Hosts,
+    /// One rule per line, in the syntax of [`DomainSet::insert`].
+    Plain,
+}
+
+/// Returns the lowercase ASCII form of a name, without its trailing dot. Labels are made of
+/// letters, digits, hyphens and underscores, and do not start or end with a hyphen.
+///
+/// With the `idna` feature, internationalized names are converted to punycode (`xn--`)
+/// labels; without it, non-ASCII names are rejected.
+fn normalize(name: &str) -> Result<String, DomainError> {
+    let invalid = || DomainError::InvalidName(name.to_string());
+    let trimmed = name.strip_suffix('.').unwrap_or(name);
+
+    #[cfg(feature = "idna")]
+    let ascii = idna::domain_to_ascii(trimmed).map_err(|_| invalid())?;
+    #[cfg(not(feature = "idna"))]
+    let ascii = if trimmed.is_ascii() {
+        trimmed.to_ascii_lowercase()
+    } else {
+        return Err(invalid());
+    };
+
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= MAX_LABEL_LEN
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+    };
+    if ascii.len() > MAX_NAME_LEN || !ascii.split('.').all(valid_label) {
+        return Err(invalid());
+    }
+    Ok(ascii)
+}
+
+/// Returns the tree key of a normalized name: its labels reversed, each followed by a dot.
+fn reversed(name: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(name.len() + 2);
+    for label in name.rsplit('.') {
+        key.extend_from_slice(label.as_bytes());
+        key.push(b'.');
+    }
+    key
+}
+
+/// Parses a rule into its normalized form and tree key.
+fn rule_key(rule: &str) -> Result<(String, Vec<u8>), DomainError> {
+    match rule.strip_prefix('.') {
+        Some(name) => {
+            let name = normalize(name).map_err(|_| DomainError::InvalidName(rule.to_string()))?;
+            let key = reversed(&name);
+            Ok((format!(".{}", name), key))
+        }
+        None => {
+            let name = normalize(rule)?;
+            let mut key = reversed(&name);
+            key.push(0);
+            Ok((name, key))
+        }
+    }
+}
+
+/// A set of domain name rules, each with a value, matching host names to their most
+/// specific rule.
+///
+/// A rule is either an exact name (`example.com`), matching only that name, or a name with
+/// a leading dot (`.example.com`), matching the name itself and all of its subdomains.
+/// Names are case-insensitive and a trailing dot is ignored. When several rules match a
+/// host, an exact rule wins, then the subdomain rule with the most labels.
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::DomainSet;
+///
+/// let mut set = DomainSet::new().unwrap();
+/// set.insert(".internal.corp", "internal").unwrap();
+/// set.insert(".build.internal.corp", "ci").unwrap();
+/// set.insert("tracker.example.com", "blocked").unwrap();
+///
+/// assert_eq!(set.lookup("db.internal.corp"), Some((".internal.corp", &"internal")));
+/// assert_eq!(set.lookup("Runner1.Build.Internal.Corp."), Some((".build.internal.corp", &"ci")));
+/// assert_eq!(set.lookup("tracker.example.com").map(|(_, v)| *v), Some("blocked"));
+/// assert_eq!(set.lookup("cdn.tracker.example.com"), None);
+/// assert_eq!(set.lookup("notinternal.corp"), None);
+/// ```
+pub struct DomainSet<V> {
+    tree: RadixTree,
+    entries: Slab<(Box<str>, V)>,
+}
+
+impl<V> DomainSet<V> {
+    /// Creates a new empty set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    pub fn new() -> Result<Self, &'static str> {
+        Ok(Self {
+            tree: RadixTree::new()?,
+            entries: Slab::new(),
+        })
+    }
+
+    /// Returns the number of rules in the set.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the set holds no rule.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a rule, returning the value it previously had, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidName`] if the rule is not a valid domain name, with an
+    /// optional leading dot, or [`DomainError::Tree`] if the tree insertion fails (on
+    /// allocation failure).
+    pub fn insert(&mut self, rule: &str, value: V) -> Result<Option<V>, DomainError> {
+        let (rule, key) = rule_key(rule)?;
+        if let Some(slot) = self.tree.find_exact(&key) {
+            let entry = self.entries.get_mut(slot).unwrap();
+            return Ok(Some(std::mem::replace(&mut entry.1, value)));
+        }
+        self.tree
+            .insert(&key, self.entries.next_value())
+            .map_err(DomainError::Tree)?;
+        self.entries.insert((rule.into_boxed_str(), value));
+        Ok(None)
+    }
+
+    /// Returns the value of a rule.
+    pub fn get(&self, rule: &str) -> Option<&V> {
+        let (_, key) = rule_key(rule).ok()?;
+        let slot = self.tree.find_exact(key)?;
+        self.entries.get(slot).map(|(_, value)| value)
+    }
+
+    /// Removes a rule, returning its value.
+    pub fn remove(&mut self, rule: &str) -> Option<V> {
+        let (_, key) = rule_key(rule).ok()?;
+        let slot = self.tree.find_exact(&key)?;
+        self.tree.remove(&key).ok()?;
+        self.entries.remove(slot).map(|(_, value)| value)
+    }
+
+    /// Returns the most specific rule matching `host`, in its normalized form, with its
+    /// value. Invalid host names match nothing.
+    pub fn lookup(&self, host: &str) -> Option<(&str, &V)> {
+        let name = normalize(host).ok()?;
+        let mut key = reversed(&name);
+        key.push(0);
+        let mut found = None;
+        self.tree.walk_prefixes(&key, |_, slot| found = Some(slot));
+        self.entries.get(found?).map(|(rule, value)| (&**rule, value))
+    }
+
+    /// Returns `true` if a rule matches `host`.
+    pub fn contains(&self, host: &str) -> bool {
+        self.lookup(host).is_some()
+    }
+
+    /// Returns all the rules with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.entries.iter().map(|(rule, value)| (&**rule, value))
+    }
+}
+
+impl<V: Clone> DomainSet<V> {
+    /// Reads a domain list, inserting all its rules with `value`, and returns the number of
+    /// rules read.
+    ///
+    /// Blank lines and comments, from `#` to the end of the line, are ignored. Rules already
+    /// in the set get the new value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DomainError::InvalidLine`] for the first line that cannot be parsed, with
+    /// the rules of the previous lines inserted, [`DomainError::Io`] if reading fails, or
+    /// [`DomainError::Tree`] if a tree insertion fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::{DomainSet, ListFormat};
+    ///
+    /// let hosts = "# blocklist\n0.0.0.0 ads.example.com tracker.example.net\n0.0.0.0 0.0.0.0\n";
+    /// let mut set = DomainSet::new().unwrap();
+    /// assert_eq!(set.load(hosts.as_bytes(), ListFormat::Hosts, "blocked").unwrap(), 2);
+    /// assert!(set.contains("ads.example.com"));
+    ///
+    /// let plain = ".internal.corp\nintranet.example.com  # exact\n";
+    /// assert_eq!(set.load(plain.as_bytes(), ListFormat::Plain, "direct").unwrap(), 2);
+    /// assert_eq!(set.lookup("git.internal.corp").map(|(_, v)| *v), Some("direct"));
+    /// ```
+    pub fn load<R: BufRead>(&mut self, reader: R, format: ListFormat, value: V) -> Result<usize, DomainError> {
+        let mut count = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| DomainError::Io(e.to_string()))?;
+            let content = line.split('#').next().unwrap_or_default().trim();
+            if content.is_empty() {
+                continue;
+            }
+            let invalid = || DomainError::InvalidLine {
+                line: index + 1,
+                content: content.to_string(),
+            };
+            let mut fields = content.split_whitespace();
+            let rules: Vec<&str> = match format {
+                ListFormat::Hosts => {
+                    if fields.next().and_then(|addr| addr.parse::<IpAddr>().ok()).is_none() {
+                        return Err(invalid());
+                    }
+                    fields.filter(|name| name.parse::<IpAddr>().is_err()).collect()
+                }
+                ListFormat::Plain => {
+                    let rule = fields.next().unwrap();
+                    if fields.next().is_some() {
+                        return Err(invalid());
+                    }
+                    vec![rule]
+                }
+            };
+            for rule in rules {
+                match self.insert(rule, value.clone()) {
+                    Ok(_) => count += 1,
+                    Err(DomainError::InvalidName(_)) => return Err(invalid()),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+        Ok(count)
+    }
+}
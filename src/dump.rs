@@ -0,0 +1,217 @@
+//! Debug dumps of the structure of a Radix Tree
+//!
+//! This model describes the nodes of a tree as a [`DumpNode`] tree, which can be printed
+//! as an indented listing or rendered as a Graphviz graph, to see how keys are laid out
+//! and why a path does or does not match.
+
+use crate::ffi::Rax;
+use crate::node::Node;
+use crate::radix_tree::RadixTree;
+use std::fmt::{self, Write};
+
+/// Layout of a rax node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A string of bytes leading to a single child.
+    Compressed,
+    /// One byte per child, in sorted order.
+    Branching,
+    /// No children.
+    Leaf,
+}
+
+/// Description of a node of a tree and of its descendants, returned by
+/// [`RadixTree::debug_dump`].
+///
+/// As in rax, a node stores the bytes leading to its children, while the key ending at the
+/// node (if any) is made of the bytes leading to the node itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpNode {
+    /// Layout of the node.
+    pub kind: NodeKind,
+    /// Bytes from the root of the tree to this node. For key nodes of a case-insensitive
+    /// tree, the key as it was inserted.
+    pub prefix: Vec<u8>,
+    /// The compressed string, or the edge bytes of a branching node.
+    pub chars: Vec<u8>,
+    /// Whether a key ends at this node.
+    pub is_key: bool,
+    /// Value of the key ending at this node, `None` for keys inserted without a value.
+    pub value: Option<i32>,
+    /// Children, in the order of `chars`.
+    pub children: Vec<DumpNode>,
+}
+
+impl DumpNode {
+    fn new(node: Node<'_>, prefix: Vec<u8>) -> DumpNode {
+        let chars = node.chars().to_vec();
+        let kind = if node.is_compressed() {
+            NodeKind::Compressed
+        } else if chars.is_empty() {
+            NodeKind::Leaf
+        } else {
+            NodeKind::Branching
+        };
+        let children = (0..node.child_count())
+            .map(|i| {
+                let mut child_prefix = prefix.clone();
+                match kind {
+                    NodeKind::Compressed => child_prefix.extend_from_slice(&chars),
+                    _ => child_prefix.push(chars[i]),
+                }
+                DumpNode::new(node.child(i), child_prefix)
+            })
+            .collect();
+        DumpNode {
+            kind,
+            is_key: node.is_key(),
+            value: node.data().map(|data| data as isize as i32),
+            prefix,
+            chars,
+            children,
+        }
+    }
+
+    /// Replaces the prefix of key nodes by the key as it was inserted.
+    fn respell(&mut self, tree: &RadixTree) {
+        if self.is_key {
+            self.prefix = tree.spelling(std::mem::take(&mut self.prefix));
+        }
+        for child in &mut self.children {
+            child.respell(tree);
+        }
+    }
+
+    fn write_listing(&self, f: &mut fmt::Formatter<'_>, depth: usize, edge: Option<u8>) -> fmt::Result {
+        write!(f, "{:indent$}", "", indent = depth * 2)?;
+        if let Some(byte) = edge {
+            write!(f, "'{}' -> ", [byte].escape_ascii())?;
+        }
+        match self.kind {
+            NodeKind::Compressed => write!(f, "\"{}\"", self.chars.escape_ascii())?,
+            _ => write!(f, "[{}]", self.chars.escape_ascii())?,
+        }
+        if self.is_key {
+            write!(f, " key=\"{}\"", self.prefix.escape_ascii())?;
+            match self.value {
+                Some(value) => write!(f, " = {}", value)?,
+                None => write!(f, " = null")?,
+            }
+        }
+        writeln!(f)?;
+        for (i, child) in self.children.iter().enumerate() {
+            let edge = (self.kind == NodeKind::Branching).then(|| self.chars[i]);
+            child.write_listing(f, depth + 1, edge)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the node as `n<id>` and its descendants as the following ids.
+    fn write_dot(&self, out: &mut String, next_id: &mut usize) {
+        let id = *next_id;
+        *next_id += 1;
+
+        let (shape, chars) = match self.kind {
+            NodeKind::Compressed => ("box", format!("\"{}\"", self.chars.escape_ascii())),
+            NodeKind::Branching => ("ellipse", format!("[{}]", self.chars.escape_ascii())),
+            NodeKind::Leaf => ("circle", String::new()),
+        };
+        let mut label = chars;
+        if self.is_key {
+            if !label.is_empty() {
+                label.push('\n');
+            }
+            match self.value {
+                Some(value) => write!(label, "= {}", value).unwrap(),
+                None => label.push_str("= null"),
+            }
+        }
+        let peripheries = if self.is_key { 2 } else { 1 };
+        writeln!(
+            out,
+            "  n{} [shape={}, peripheries={}, label=\"{}\"];",
+            id,
+            shape,
+            peripheries,
+            dot_escape(&label)
+        )
+        .unwrap();
+
+        for (i, child) in self.children.iter().enumerate() {
+            let child_id = *next_id;
+            child.write_dot(out, next_id);
+            match self.kind {
+                NodeKind::Branching => {
+                    let edge = format!("{}", [self.chars[i]].escape_ascii());
+                    writeln!(out, "  n{} -> n{} [label=\"{}\"];", id, child_id, dot_escape(&edge)).unwrap();
+                }
+                _ => writeln!(out, "  n{} -> n{};", id, child_id).unwrap(),
+            }
+        }
+    }
+}
+
+/// Prints the node and its descendants, one node per line, indented by depth.
+impl fmt::Display for DumpNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_listing(f, 0, None)
+    }
+}
+
+/// Escapes a label for a double-quoted DOT string, with `\n` line breaks.
+fn dot_escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+impl RadixTree {
+    /// Describes the structure of the tree, starting from its head node.
+    ///
+    /// The result prints as an indented listing, one node per line: compressed nodes as
+    /// `"chars"`, branching nodes as `[chars]` with one `'c' ->` line per child, and key
+    /// nodes with their key and value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api", 1), ("/api/users", 2), ("/api/posts", 3)]).unwrap();
+    ///
+    /// let dump = tree.debug_dump();
+    /// assert_eq!(dump.chars, b"/api");
+    /// assert_eq!(dump.children[0].value, Some(1));
+    /// print!("{}", dump);
+    /// ```
+    pub fn debug_dump(&self) -> DumpNode {
+        let rax = self.tree as *const Rax;
+        let mut dump = DumpNode::new(unsafe { Node::head(rax) }, Vec::new());
+        dump.respell(self);
+        dump
+    }
+
+    /// Renders the structure of the tree as a Graphviz graph.
+    ///
+    /// Compressed nodes are boxes, branching nodes ellipses and leaves circles. Key nodes
+    /// have a double border and show their value, and the edges of branching nodes are
+    /// labeled with their byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert("/api", 1).unwrap();
+    ///
+    /// let dot = tree.to_dot();
+    /// assert!(dot.starts_with("digraph radix_tree {"));
+    /// // Render with: dot -Tsvg tree.dot -o tree.svg
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph radix_tree {\n  node [fontname=\"monospace\"];\n");
+        self.debug_dump().write_dot(&mut out, &mut 0);
+        out.push_str("}\n");
+        out
+    }
+}
@@ -11,10 +11,12 @@ const RAX_ITER_STATIC_LEN: usize = 128;
 /// Size of the static stack in RaxStack
 const RAX_STACK_STATIC_ITEMS: usize = 32;
 
-/// Opaque type representing the Rax tree structure
+/// The Rax tree structure
 #[repr(C)]
 pub struct Rax {
-    _private: [u8; 0],
+    pub head: *mut RaxNode,
+    pub numele: u64,
+    pub numnodes: u64,
 }
 
 /// Opaque type representing a node in the Rax tree
@@ -65,6 +67,24 @@ extern "C" {
     pub fn raxPrev(it: *mut RaxIterator) -> c_int;
     pub fn raxUp(it: *mut RaxIterator) -> c_int;
     pub fn raxStop(it: *mut RaxIterator);
+    pub fn raxGetData(n: *mut RaxNode) -> *mut c_void;
+
+    // Low level node access (see the end of rax.c)
+    pub fn raxNodeIsKey(n: *mut RaxNode) -> c_int;
+    pub fn raxNodeIsNull(n: *mut RaxNode) -> c_int;
+    pub fn raxNodeIsCompr(n: *mut RaxNode) -> c_int;
+    pub fn raxNodeSize(n: *mut RaxNode) -> usize;
+    pub fn raxNodeChars(n: *mut RaxNode) -> *mut c_uchar;
+    pub fn raxNodeChild(n: *mut RaxNode, i: usize) -> *mut RaxNode;
+    pub fn raxNodeSetChild(n: *mut RaxNode, i: usize, child: *mut RaxNode);
+    pub fn raxNodeCreate(
+        iscompr: c_int,
+        size: usize,
+        chars: *const c_uchar,
+        iskey: c_int,
+        data: *mut c_void,
+    ) -> *mut RaxNode;
+    pub fn raxNodeFree(n: *mut RaxNode) -> u64;
     pub static mut raxNotFound: *mut c_void;
 }
 
@@ -166,6 +186,14 @@ pub unsafe fn tree_up_raw(iter: *mut c_void, buf: *const u8, len: usize) -> c_in
     }
 }
 
+/// Seeks `iter` to `buf` and returns the value of its longest prefix, or -1.
+pub unsafe fn tree_longest_prefix_raw(tree: *mut c_void, iter: *mut c_void, buf: *const u8, len: usize) -> c_int {
+    if tree_search_raw(tree, iter, buf, len).is_null() {
+        return -1;
+    }
+    tree_up_raw(iter, buf, len)
+}
+
 pub unsafe fn tree_stop_raw(iter: *mut c_void) -> c_int {
     if iter.is_null() {
         return 0;
@@ -174,3 +202,17 @@ pub unsafe fn tree_stop_raw(iter: *mut c_void) -> c_int {
     0
 }
 
+pub unsafe fn tree_seek_first_raw(iter: *mut c_void) -> c_int {
+    if iter.is_null() {
+        return 0;
+    }
+    static OP_FIRST: [c_uchar; 2] = [b'^', 0];
+    raxSeek(iter as *mut RaxIterator, OP_FIRST.as_ptr(), ptr::null(), 0)
+}
+
+pub unsafe fn tree_next_raw(iter: *mut c_void) -> c_int {
+    if iter.is_null() {
+        return 0;
+    }
+    raxNext(iter as *mut RaxIterator)
+}
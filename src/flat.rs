@@ -0,0 +1,429 @@
+//! Flattened read-only trees
+//!
+//! This model provides a position-independent, read-only layout of a tree that can be
+//! written to a file and memory-mapped by many processes at once. Lookups run directly
+//! on the mapped bytes: nothing is deserialized or allocated when a tree is opened.
+//!
+//! # Format
+//!
+//! All integers are little-endian, and all offsets are relative to the start of the buffer.
+//!
+//! ```text
+//! magic     4 bytes   "RRMF"
+//! version   u16       FLAT_VERSION
+//! flags     u16       case folding: 0 sensitive, 1 ASCII, 2 Unicode; other bits reserved, 0
+//! numele    u64       number of keys
+//! root      u32       offset of the head node
+//! len       u32       total length of the buffer
+//! ```
+//!
+//! Nodes are 4-byte aligned and laid out in depth-first pre-order, so every child lives
+//! at a higher offset than its parent:
+//!
+//! ```text
+//! header    u32       iskey | hasvalue << 1 | iscompr << 2 | size << 3
+//! value     i32       route index, 0 when the node has no value
+//! chars     size      compressed string, or one sorted edge byte per child
+//! padding   0-3       up to the next multiple of 4
+//! children  u32 * n   child offsets, n = 1 for compressed nodes, size otherwise
+//! ```
+
+use crate::case::CaseFolding;
+use crate::node::Node;
+use crate::radix_tree::{Match, RadixTree};
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+const FLAT_MAGIC: &[u8; 4] = b"RRMF";
+const FLAT_VERSION: u16 = 1;
+const HEADER_LEN: usize = 4 + 2 + 2 + 8 + 4 + 4;
+
+const NODE_ISKEY: u32 = 1 << 0;
+const NODE_HASVALUE: u32 = 1 << 1;
+const NODE_ISCOMPR: u32 = 1 << 2;
+const NODE_SIZE_SHIFT: u32 = 3;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn align4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Encodes the tree rooted at `head` in the flattened layout.
+fn flatten(head: Node<'_>, numele: u64, folding: CaseFolding) -> io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(HEADER_LEN);
+    out.extend_from_slice(FLAT_MAGIC);
+    out.extend_from_slice(&FLAT_VERSION.to_le_bytes());
+    out.extend_from_slice(&folding.flags().to_le_bytes());
+    out.extend_from_slice(&numele.to_le_bytes());
+    out.extend_from_slice(&(HEADER_LEN as u32).to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+
+    // Stack of (node, position of the parent slot holding its offset).
+    let mut stack = vec![(head, None)];
+    while let Some((node, slot)) = stack.pop() {
+        let offset = out.len();
+        let offset32 = u32::try_from(offset).map_err(|_| invalid("flattened tree exceeds 4 GiB"))?;
+        if let Some(slot) = slot {
+            out[slot..slot + 4].copy_from_slice(&offset32.to_le_bytes());
+        }
+
+        let chars = node.chars();
+        let value = node.data().map(|data| data as isize as i32).unwrap_or(0);
+        let mut header = (chars.len() as u32) << NODE_SIZE_SHIFT;
+        if node.is_key() {
+            header |= NODE_ISKEY;
+        }
+        if value != 0 {
+            header |= NODE_HASVALUE;
+        }
+        if node.is_compressed() {
+            header |= NODE_ISCOMPR;
+        }
+        out.extend_from_slice(&header.to_le_bytes());
+        out.extend_from_slice(&value.to_le_bytes());
+        out.extend_from_slice(chars);
+        out.resize(align4(out.len()), 0);
+
+        let children = out.len();
+        out.resize(children + 4 * node.child_count(), 0);
+        for i in (0..node.child_count()).rev() {
+            stack.push((node.child(i), Some(children + 4 * i)));
+        }
+    }
+
+    let len = u32::try_from(out.len()).map_err(|_| invalid("flattened tree exceeds 4 GiB"))?;
+    out[HEADER_LEN - 4..HEADER_LEN].copy_from_slice(&len.to_le_bytes());
+    Ok(out)
+}
+
+/// A node decoded from a flattened buffer.
+struct FlatNode<'a> {
+    header: u32,
+    value: i32,
+    chars: &'a [u8],
+    children: &'a [u8],
+}
+
+impl FlatNode<'_> {
+    fn value(&self) -> Option<i32> {
+        if self.header & NODE_ISKEY != 0 && self.header & NODE_HASVALUE != 0 {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    fn is_compressed(&self) -> bool {
+        self.header & NODE_ISCOMPR != 0
+    }
+
+    fn child(&self, i: usize) -> Option<u32> {
+        let bytes = self.children.get(4 * i..4 * i + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+/// A read-only Radix Tree stored in a flat, position-independent byte buffer.
+///
+/// The buffer can be any byte container: a `Vec<u8>`, a borrowed slice, or a memory
+/// mapping obtained with [`FlatTree::open`]. Lookups never allocate (except for the result
+/// of [`FlatTree::find_all_prefixes`], and for folding paths that change under the case
+/// folding of the tree) and do not need an iterator.
+///
+/// A flattened case-insensitive tree folds paths as the tree it was written from does.
+/// It does not keep the spelling keys were inserted with, so the keys of its matches are
+/// spelled as in the path.
+///
+/// Lookups are bounds-checked: a corrupted buffer makes them return `None` rather than
+/// read out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::{FlatTree, RadixTree};
+///
+/// let mut tree = RadixTree::new().unwrap();
+/// tree.insert("/api", 1).unwrap();
+/// tree.insert("/api/users", 2).unwrap();
+///
+/// let mut buf = Vec::new();
+/// tree.write_flat(&mut buf).unwrap();
+///
+/// let flat = FlatTree::new(buf).unwrap();
+/// assert_eq!(flat.find_exact("/api"), Some(1));
+/// assert_eq!(flat.longest_prefix("/api/users/123").unwrap().value, 2);
+/// assert_eq!(flat.find_all_prefixes("/api/users/123").len(), 2);
+/// ```
+pub struct FlatTree<B: AsRef<[u8]>> {
+    buf: B,
+    numele: u64,
+    root: u32,
+    folding: CaseFolding,
+}
+
+impl<B: AsRef<[u8]>> FlatTree<B> {
+    /// Wraps a buffer written by [`RadixTree::write_flat`].
+    ///
+    /// Only the header is checked, so this is O(1) regardless of the tree size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`io::ErrorKind::InvalidData`] if the header is missing, was written by an
+    /// unsupported version or with unknown flags, or does not match the buffer length.
+    pub fn new(buf: B) -> io::Result<Self> {
+        let bytes = buf.as_ref();
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != FLAT_MAGIC {
+            return Err(invalid("not a flattened radix tree"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != FLAT_VERSION {
+            return Err(invalid("unsupported flattened tree version"));
+        }
+        let folding = CaseFolding::from_flags(u16::from_le_bytes([bytes[6], bytes[7]]))
+            .ok_or_else(|| invalid("unsupported flattened tree flags"))?;
+        let numele = u64::from_le_bytes(bytes[8..16].try_into().unwrap());
+        let root = u32::from_le_bytes(bytes[16..20].try_into().unwrap());
+        let len = u32::from_le_bytes(bytes[20..24].try_into().unwrap());
+        if len as usize != bytes.len() {
+            return Err(invalid("flattened tree length mismatch"));
+        }
+        if root as usize >= bytes.len() {
+            return Err(invalid("flattened tree root out of bounds"));
+        }
+        Ok(Self {
+            buf,
+            numele,
+            root,
+            folding,
+        })
+    }
+
+    /// Returns the number of keys stored in the tree.
+    pub fn len(&self) -> u64 {
+        self.numele
+    }
+
+    /// Returns `true` if the tree holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.numele == 0
+    }
+
+    /// Returns the comparison of keys of the tree the buffer was written from.
+    pub fn case_folding(&self) -> CaseFolding {
+        self.folding
+    }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+
+    fn node(&self, offset: u32) -> Option<FlatNode<'_>> {
+        let bytes = self.buf.as_ref();
+        let start = offset as usize;
+        let header = u32::from_le_bytes(bytes.get(start..start + 4)?.try_into().unwrap());
+        let value = i32::from_le_bytes(bytes.get(start + 4..start + 8)?.try_into().unwrap());
+        let size = (header >> NODE_SIZE_SHIFT) as usize;
+        let chars = bytes.get(start + 8..start + 8 + size)?;
+        let children_start = align4(start + 8 + size);
+        let count = if header & NODE_ISCOMPR != 0 { 1 } else { size };
+        let children = bytes.get(children_start..children_start + 4 * count)?;
+        Some(FlatNode {
+            header,
+            value,
+            chars,
+            children,
+        })
+    }
+
+    /// Walks `path` from the root, calling `visit(matched_len, value)` for every key
+    /// that is a prefix of `path`, from the shortest to the longest.
+    fn walk(&self, original: &[u8], mut visit: impl FnMut(usize, i32)) {
+        let path = &*self.folding.fold(original);
+        let mut offset = self.root;
+        let mut pos = 0;
+        while let Some(node) = self.node(offset) {
+            if let Some(value) = node.value() {
+                visit(self.folding.original_len(original, pos), value);
+            }
+            if node.chars.is_empty() {
+                return;
+            }
+            let next = if node.is_compressed() {
+                if !path[pos..].starts_with(node.chars) {
+                    return;
+                }
+                pos += node.chars.len();
+                node.child(0)
+            } else {
+                let Some(&byte) = path.get(pos) else {
+                    return;
+                };
+                let Ok(i) = node.chars.binary_search(&byte) else {
+                    return;
+                };
+                pos += 1;
+                node.child(i)
+            };
+            // Children always follow their parent, which guarantees termination
+            // even on corrupted buffers.
+            match next {
+                Some(child) if child > offset => offset = child,
+                _ => return,
+            }
+        }
+    }
+
+    /// Finds the exact match for a path.
+    ///
+    /// Returns `Some(idx)` if the path exists, `None` otherwise.
+    pub fn find_exact<K: AsRef<[u8]>>(&self, path: K) -> Option<i32> {
+        let path = path.as_ref();
+        let mut found = None;
+        self.walk(path, |len, value| {
+            if len == path.len() {
+                found = Some(value);
+            }
+        });
+        found
+    }
+
+    /// Finds the longest prefix match for a path.
+    ///
+    /// Returns the [`Match`] of the longest matching prefix, `None` if no match.
+    pub fn longest_prefix<'p, K: AsRef<[u8]> + ?Sized>(&self, path: &'p K) -> Option<Match<'p>> {
+        let path = path.as_ref();
+        let mut found = None;
+        self.walk(path, |len, value| found = Some((len, value)));
+        found.map(|(len, value)| Match::new(value, Cow::Borrowed(&path[..len]), len, path))
+    }
+
+    /// Returns all matching prefixes for a path, from longest to shortest.
+    pub fn find_all_prefixes<'p, K: AsRef<[u8]> + ?Sized>(&self, path: &'p K) -> Vec<Match<'p>> {
+        let path = path.as_ref();
+        let mut results = Vec::new();
+        self.walk(path, |len, value| {
+            results.push(Match::new(value, Cow::Borrowed(&path[..len]), len, path))
+        });
+        results.reverse();
+        results
+    }
+}
+
+#[cfg(unix)]
+impl FlatTree<Mmap> {
+    /// Memory-maps a file written by [`RadixTree::write_flat`].
+    ///
+    /// The mapping is read-only and shared, so any number of processes can map the same
+    /// file while the kernel keeps a single copy of it in the page cache.
+    ///
+    /// To publish a new version of a mapped file, write it to a new file and `rename` it
+    /// over the old one; open trees keep mapping the old file until they are dropped.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this process or any other, while the
+    /// tree is alive: see [`Mmap::map`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, or is not a valid
+    /// flattened tree.
+    pub unsafe fn open<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        FlatTree::new(Mmap::map(&file)?)
+    }
+}
+
+impl RadixTree {
+    /// Writes the tree in the flattened read-only layout used by [`FlatTree`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported by the writer, or [`io::ErrorKind::InvalidData`] if the
+    /// flattened tree would exceed 4 GiB.
+    pub fn write_flat<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let rax = self.tree as *const crate::ffi::Rax;
+        let buf = unsafe { flatten(Node::head(rax), (*rax).numele, self.case_folding())? };
+        writer.write_all(&buf)
+    }
+}
+
+/// A read-only, shared memory mapping of a whole file.
+#[cfg(unix)]
+pub struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+#[cfg(unix)]
+impl Mmap {
+    /// Maps the whole file read-only.
+    ///
+    /// # Safety
+    ///
+    /// The mapping is shared with the file, and is exposed as a `&[u8]` that Rust assumes
+    /// never changes. The file must therefore not be modified or truncated, by this process
+    /// or any other, until the mapping is dropped: rewritten bytes would change under
+    /// live references, which is undefined behavior, and accessing pages removed by a
+    /// truncation raises `SIGBUS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file metadata cannot be read or `mmap` fails.
+    pub unsafe fn map(file: &std::fs::File) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let len = usize::try_from(file.metadata()?.len()).map_err(|_| invalid("file too large to map"))?;
+        if len == 0 {
+            return Ok(Self {
+                ptr: std::ptr::null_mut(),
+                len,
+            });
+        }
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { ptr, len })
+    }
+}
+
+#[cfg(unix)]
+impl AsRef<[u8]> for Mmap {
+    fn as_ref(&self) -> &[u8] {
+        if self.len == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        if self.len != 0 {
+            unsafe {
+                libc::munmap(self.ptr, self.len);
+            }
+        }
+    }
+}
+
+// The mapping is read-only, so it can be shared and sent between threads.
+#[cfg(unix)]
+unsafe impl Send for Mmap {}
+#[cfg(unix)]
+unsafe impl Sync for Mmap {}
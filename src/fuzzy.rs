@@ -0,0 +1,63 @@
+//! Approximate lookups on the Radix Tree
+//!
+//! The walk carries one row of the Levenshtein distance table per byte of a key: the
+//! distances between the key so far and every prefix of the searched path. Keys sharing a
+//! prefix share these rows, and a subtree is skipped as soon as every distance in the row
+//! exceeds the bound, since appending bytes can only make them larger.
+
+use crate::node::Cursor;
+use crate::radix_tree::RadixTree;
+
+impl RadixTree {
+    /// Returns the keys within `max_distance` edits of `path`, with their values and
+    /// distances, ordered by distance then lexicographically.
+    ///
+    /// The distance is the Levenshtein distance over bytes: the number of single byte
+    /// insertions, deletions and substitutions turning a key into `path`. A non-ASCII
+    /// character that differs counts for each of its differing UTF-8 bytes. A
+    /// case-insensitive tree compares folded keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api/users", 1), ("/api/posts", 2), ("/admin/users", 3)]).unwrap();
+    ///
+    /// let suggestions = tree.fuzzy_find("/api/user", 2);
+    /// assert_eq!(suggestions, vec![(b"/api/users".to_vec(), 1, 1)]);
+    /// assert_eq!(tree.fuzzy_find("/api/usres", 2)[0].0, b"/api/users");
+    /// assert!(tree.fuzzy_find("/static", 2).is_empty());
+    /// ```
+    pub fn fuzzy_find<K: AsRef<[u8]>>(&self, path: K, max_distance: usize) -> Vec<(Vec<u8>, i32, usize)> {
+        let path = &*self.fold(path.as_ref());
+        let mut found = Vec::new();
+        // Distances from the empty key to each prefix of the path.
+        let first_row: Vec<usize> = (0..=path.len()).collect();
+        let mut stack = vec![(Cursor::new(self), Vec::new(), first_row)];
+        while let Some((cursor, key, row)) = stack.pop() {
+            let distance = row[path.len()];
+            if distance <= max_distance {
+                if let Some(value) = cursor.value() {
+                    found.push((self.spelling(key.clone()), value, distance));
+                }
+            }
+            for (byte, next) in cursor.children() {
+                let mut next_row = Vec::with_capacity(row.len());
+                next_row.push(row[0] + 1);
+                for (j, &expected) in path.iter().enumerate() {
+                    let substitution = row[j] + (expected != byte) as usize;
+                    next_row.push(substitution.min(row[j + 1] + 1).min(next_row[j] + 1));
+                }
+                if next_row.iter().min().is_some_and(|&min| min <= max_distance) {
+                    let mut next_key = key.clone();
+                    next_key.push(byte);
+                    stack.push((next, next_key, next_row));
+                }
+            }
+        }
+        found.sort_by(|(a, _, a_distance), (b, _, b_distance)| (a_distance, a).cmp(&(b_distance, b)));
+        found
+    }
+}
@@ -0,0 +1,445 @@
+//! Glob pattern matching built on the Radix Tree
+//!
+//! Patterns are stored under their literal prefix, the part before the first wildcard, so
+//! that matching a path only considers the patterns whose literal prefix is a prefix of
+//! the path: one tree walk finds them all, and each candidate is then matched against the
+//! rest of the path.
+//!
+//! The reverse query, [`RadixTree::find_matching`], runs a pattern as a state machine
+//! along the tree, byte by byte, and prunes the subtrees where no state is left.
+
+use crate::node::Cursor;
+use crate::radix_tree::RadixTree;
+use crate::slab::Slab;
+use std::cmp::{Ordering, Reverse};
+use std::fmt;
+
+/// Error returned for invalid glob patterns, or when a [`GlobRouter`] cannot be updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobError {
+    /// A pattern ends with a lone `\`, or has more than two consecutive `*`.
+    InvalidPattern(String),
+    /// The underlying tree rejected the insertion.
+    Tree(i32),
+}
+
+impl fmt::Display for GlobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GlobError::InvalidPattern(pattern) => write!(f, "invalid glob pattern {:?}", pattern),
+            GlobError::Tree(rc) => write!(f, "radix tree insertion failed with error {}", rc),
+        }
+    }
+}
+
+impl std::error::Error for GlobError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    /// `?`: one character other than `/`.
+    AnyChar,
+    /// `*`: any characters other than `/`.
+    Star,
+    /// `**`: any characters.
+    DoubleStar,
+    /// `**/` as a whole segment: zero or more whole segments.
+    AnyDirs,
+}
+
+/// A parsed glob pattern.
+struct Glob {
+    pattern: Box<str>,
+    tokens: Vec<Token>,
+    /// Number of literal bytes, `**` and `*`/`?` wildcards, compared for specificity.
+    literal_len: usize,
+    double_stars: usize,
+    stars: usize,
+}
+
+impl Glob {
+    fn parse(pattern: &str) -> Result<Glob, GlobError> {
+        let invalid = || GlobError::InvalidPattern(pattern.to_string());
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+        // Whether the next character starts a path segment.
+        let mut segment_start = true;
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '\\' => {
+                    literal.push(chars.next().ok_or_else(invalid)?);
+                    segment_start = false;
+                    continue;
+                }
+                '?' => Token::AnyChar,
+                '*' if chars.peek() != Some(&'*') => Token::Star,
+                '*' => {
+                    chars.next();
+                    if chars.peek() == Some(&'*') {
+                        return Err(invalid());
+                    }
+                    if segment_start && chars.peek() == Some(&'/') {
+                        chars.next();
+                        Token::AnyDirs
+                    } else {
+                        Token::DoubleStar
+                    }
+                }
+                _ => {
+                    literal.push(c);
+                    segment_start = c == '/';
+                    continue;
+                }
+            };
+            if !literal.is_empty() {
+                tokens.push(Token::Literal(std::mem::take(&mut literal)));
+            }
+            segment_start = token == Token::AnyDirs;
+            tokens.push(token);
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        let count = |f: fn(&Token) -> bool| tokens.iter().filter(|token| f(token)).count();
+        Ok(Glob {
+            pattern: pattern.into(),
+            literal_len: tokens
+                .iter()
+                .map(|token| match token {
+                    Token::Literal(literal) => literal.len(),
+                    _ => 0,
+                })
+                .sum(),
+            double_stars: count(|token| matches!(token, Token::DoubleStar | Token::AnyDirs)),
+            stars: count(|token| matches!(token, Token::Star | Token::AnyChar)),
+            tokens,
+        })
+    }
+
+    /// Returns the literal characters before the first wildcard.
+    fn literal_prefix(&self) -> &str {
+        match self.tokens.first() {
+            Some(Token::Literal(literal)) => literal,
+            _ => "",
+        }
+    }
+
+    /// Orders the most specific patterns first: more literal characters, then fewer `**`,
+    /// then fewer `*` and `?`, then by pattern.
+    fn specificity(&self, other: &Glob) -> Ordering {
+        (Reverse(self.literal_len), self.double_stars, self.stars, &self.pattern).cmp(&(
+            Reverse(other.literal_len),
+            other.double_stars,
+            other.stars,
+            &other.pattern,
+        ))
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let mut failed = vec![false; (self.tokens.len() + 1) * (path.len() + 1)];
+        self.match_from(0, path, 0, &mut failed)
+    }
+
+    /// Matches the tokens from `t` against the path from `p`. States known to fail are
+    /// recorded in `failed`, which keeps the matching time polynomial.
+    fn match_from(&self, t: usize, path: &str, p: usize, failed: &mut [bool]) -> bool {
+        let Some(token) = self.tokens.get(t) else {
+            return p == path.len();
+        };
+        let state = t * (path.len() + 1) + p;
+        if failed[state] {
+            return false;
+        }
+        let rest = &path[p..];
+        let matched = match token {
+            Token::Literal(literal) => {
+                rest.starts_with(literal.as_str()) && self.match_from(t + 1, path, p + literal.len(), failed)
+            }
+            Token::AnyChar => match rest.chars().next() {
+                Some(c) if c != '/' => self.match_from(t + 1, path, p + c.len_utf8(), failed),
+                _ => false,
+            },
+            Token::Star => {
+                let segment = rest.find('/').unwrap_or(rest.len());
+                (0..=segment)
+                    .filter(|&len| rest.is_char_boundary(len))
+                    .any(|len| self.match_from(t + 1, path, p + len, failed))
+            }
+            Token::DoubleStar => (0..=rest.len())
+                .filter(|&len| rest.is_char_boundary(len))
+                .any(|len| self.match_from(t + 1, path, p + len, failed)),
+            Token::AnyDirs => {
+                self.match_from(t + 1, path, p, failed)
+                    || rest
+                        .match_indices('/')
+                        .any(|(i, _)| self.match_from(t + 1, path, p + i + 1, failed))
+            }
+        };
+        if !matched {
+            failed[state] = true;
+        }
+        matched
+    }
+}
+
+/// A state of a pattern run byte by byte: a token index, with the offset reached in a
+/// literal, the continuation bytes still expected by `?`, or 1 inside a segment of `**/`.
+type State = (usize, usize);
+
+impl Glob {
+    /// Adds the states reachable without consuming a byte: past `*`, `**` and `**/`, which
+    /// can match nothing. Returns the states sorted and deduplicated.
+    fn closure(&self, mut states: Vec<State>) -> Vec<State> {
+        let mut i = 0;
+        while i < states.len() {
+            let (t, o) = states[i];
+            if o == 0
+                && matches!(
+                    self.tokens.get(t),
+                    Some(Token::Star | Token::DoubleStar | Token::AnyDirs)
+                )
+            {
+                states.push((t + 1, 0));
+            }
+            i += 1;
+        }
+        states.sort_unstable();
+        states.dedup();
+        states
+    }
+
+    /// Returns the states reached from `states` by consuming `byte`.
+    fn advance(&self, states: &[State], byte: u8) -> Vec<State> {
+        let continuation = byte & 0xc0 == 0x80;
+        let next = states.iter().filter_map(|&(t, o)| match self.tokens.get(t)? {
+            Token::Literal(literal) => {
+                let literal = literal.as_bytes();
+                (literal[o] == byte).then(|| if o + 1 == literal.len() { (t + 1, 0) } else { (t, o + 1) })
+            }
+            Token::AnyChar if o == 0 => {
+                let width = match byte {
+                    b'/' => return None,
+                    _ if continuation => return None,
+                    0xf0.. => 4,
+                    0xe0.. => 3,
+                    0xc0.. => 2,
+                    _ => 1,
+                };
+                Some(if width == 1 { (t + 1, 0) } else { (t, width - 1) })
+            }
+            Token::AnyChar => continuation.then(|| if o == 1 { (t + 1, 0) } else { (t, o - 1) }),
+            Token::Star => (byte != b'/').then_some((t, 0)),
+            Token::DoubleStar => Some((t, 0)),
+            Token::AnyDirs => Some((t, (byte != b'/') as usize)),
+        });
+        self.closure(next.collect())
+    }
+
+    fn is_accepting(&self, states: &[State]) -> bool {
+        states.contains(&(self.tokens.len(), 0))
+    }
+}
+
+impl RadixTree {
+    /// Returns the keys matching a glob pattern with their values, in lexicographic order.
+    ///
+    /// Patterns have the syntax of [`GlobRouter`]. The walk follows the pattern down the
+    /// tree and skips the subtrees that cannot match, so a pattern with a literal prefix
+    /// only visits the keys under that prefix. Keys are yielded as they are found. A
+    /// case-insensitive tree matches the pattern case-insensitively too.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GlobError::InvalidPattern`] if the pattern ends with a lone `\` or has
+    /// more than two consecutive `*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api/v1/users", 1), ("/api/v2/users", 2), ("/api/v2/users/me", 3), ("/admin/users", 4)])
+    ///     .unwrap();
+    ///
+    /// let matched: Vec<_> = tree.find_matching("/api/*/users").unwrap().collect();
+    /// assert_eq!(matched, vec![(b"/api/v1/users".to_vec(), 1), (b"/api/v2/users".to_vec(), 2)]);
+    /// assert_eq!(tree.find_matching("/**/users").unwrap().count(), 3);
+    /// ```
+    pub fn find_matching(&self, pattern: &str) -> Result<impl Iterator<Item = (Vec<u8>, i32)> + '_, GlobError> {
+        // Folding maps whole characters, so the folded pattern is valid UTF-8.
+        let glob = Glob::parse(&String::from_utf8_lossy(&self.fold(pattern.as_bytes())))?;
+        let start = glob.closure(vec![(0, 0)]);
+        let mut stack = vec![(Cursor::new(self), Vec::new(), start)];
+        Ok(std::iter::from_fn(move || {
+            while let Some((cursor, key, states)) = stack.pop() {
+                let children: Vec<_> = cursor
+                    .children()
+                    .filter_map(|(byte, next)| {
+                        let states = glob.advance(&states, byte);
+                        if states.is_empty() {
+                            return None;
+                        }
+                        let mut key = key.clone();
+                        key.push(byte);
+                        Some((next, key, states))
+                    })
+                    .collect();
+                stack.extend(children.into_iter().rev());
+                if glob.is_accepting(&states) {
+                    if let Some(value) = cursor.value() {
+                        return Some((self.spelling(key), value));
+                    }
+                }
+            }
+            None
+        }))
+    }
+}
+
+/// A set of glob patterns, each with a value, matching paths to all the patterns they
+/// match, most specific first.
+///
+/// Patterns match whole paths. `?` matches one character and `*` any number of
+/// characters, both without crossing a `/`; `**` matches any number of characters,
+/// including `/`, and a `**/` segment matches zero or more whole segments. A `\` makes
+/// the next character literal.
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::GlobRouter;
+///
+/// let mut acl = GlobRouter::new().unwrap();
+/// acl.insert("/repos/*/settings/**", "admin").unwrap();
+/// acl.insert("/repos/public/settings/**", "maintainer").unwrap();
+/// acl.insert("/repos/**", "read").unwrap();
+///
+/// let matched: Vec<_> = acl.matches("/repos/public/settings/hooks/1").into_iter().map(|(_, v)| *v).collect();
+/// assert_eq!(matched, vec!["maintainer", "admin", "read"]);
+/// assert_eq!(acl.best_match("/repos/app/settings/keys").map(|(_, v)| *v), Some("admin"));
+/// assert_eq!(acl.best_match("/users/app"), None);
+/// ```
+pub struct GlobRouter<V> {
+    tree: RadixTree,
+    /// Patterns grouped by literal prefix, most specific first.
+    buckets: Slab<Vec<(Glob, V)>>,
+    len: usize,
+}
+
+impl<V> GlobRouter<V> {
+    /// Creates a new empty router.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    pub fn new() -> Result<Self, &'static str> {
+        Ok(Self {
+            tree: RadixTree::new()?,
+            buckets: Slab::new(),
+            len: 0,
+        })
+    }
+
+    /// Returns the number of patterns in the router.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the router holds no pattern.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a pattern, returning the value it previously had, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GlobError::InvalidPattern`] if the pattern ends with a lone `\` or has
+    /// more than two consecutive `*`, or [`GlobError::Tree`] if the tree insertion fails
+    /// (on allocation failure).
+    pub fn insert(&mut self, pattern: &str, value: V) -> Result<Option<V>, GlobError> {
+        let glob = Glob::parse(pattern)?;
+        let prefix = glob.literal_prefix();
+        let slot = match self.tree.find_exact(prefix) {
+            Some(slot) => slot,
+            None => {
+                self.tree
+                    .insert(prefix, self.buckets.next_value())
+                    .map_err(GlobError::Tree)?;
+                self.buckets.insert(Vec::new())
+            }
+        };
+        let bucket = self.buckets.get_mut(slot).unwrap();
+        match bucket.binary_search_by(|(other, _)| other.specificity(&glob)) {
+            Ok(i) => Ok(Some(std::mem::replace(&mut bucket[i].1, value))),
+            Err(i) => {
+                bucket.insert(i, (glob, value));
+                self.len += 1;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the bucket of a pattern and its position in the bucket.
+    fn position(&self, pattern: &str) -> Option<(i32, usize)> {
+        let glob = Glob::parse(pattern).ok()?;
+        let slot = self.tree.find_exact(glob.literal_prefix())?;
+        let bucket = self.buckets.get(slot)?;
+        let i = bucket.binary_search_by(|(other, _)| other.specificity(&glob)).ok()?;
+        Some((slot, i))
+    }
+
+    /// Returns the value of a pattern.
+    pub fn get(&self, pattern: &str) -> Option<&V> {
+        let (slot, i) = self.position(pattern)?;
+        self.buckets.get(slot).map(|bucket| &bucket[i].1)
+    }
+
+    /// Removes a pattern, returning its value.
+    pub fn remove(&mut self, pattern: &str) -> Option<V> {
+        let (slot, i) = self.position(pattern)?;
+        let bucket = self.buckets.get_mut(slot)?;
+        let (glob, value) = bucket.remove(i);
+        if bucket.is_empty() {
+            self.tree.remove(glob.literal_prefix()).ok()?;
+            self.buckets.remove(slot);
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Returns all the patterns matching `path` with their values, most specific first.
+    ///
+    /// A pattern is more specific than another if it has more literal characters, then if
+    /// it has fewer `**`, then if it has fewer `*` and `?`; remaining ties are ordered by
+    /// pattern.
+    pub fn matches(&self, path: &str) -> Vec<(&str, &V)> {
+        let mut matched: Vec<&(Glob, V)> = Vec::new();
+        self.tree.walk_prefixes(path.as_bytes(), |_, slot| {
+            if let Some(bucket) = self.buckets.get(slot) {
+                matched.extend(bucket.iter().filter(|(glob, _)| glob.is_match(path)));
+            }
+        });
+        matched.sort_by(|(a, _), (b, _)| a.specificity(b));
+        matched
+            .into_iter()
+            .map(|(glob, value)| (&*glob.pattern, value))
+            .collect()
+    }
+
+    /// Returns the most specific pattern matching `path`, with its value.
+    pub fn best_match(&self, path: &str) -> Option<(&str, &V)> {
+        self.matches(path).into_iter().next()
+    }
+
+    /// Returns all the patterns with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.buckets
+            .iter()
+            .flatten()
+            .map(|(glob, value)| (&*glob.pattern, value))
+    }
+}
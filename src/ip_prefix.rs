@@ -0,0 +1,404 @@
+//! IP prefix (CIDR) tables built on the Radix Tree
+//!
+//! rax compares keys byte by byte, while IP prefixes have bit granularity. This model
+//! stores every prefix under a key made of a family byte followed by one byte per prefix
+//! bit, so that the longest key that is a prefix of an address key is the longest
+//! matching IP prefix. IPv4 and IPv6 prefixes live side by side in the same tree.
+
+use crate::radix_tree::RadixTree;
+use crate::slab::Slab;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+const FAMILY_V4: u8 = 4;
+const FAMILY_V6: u8 = 6;
+
+/// An IPv4 or IPv6 prefix, such as `10.0.0.0/8` or `2001:db8::/32`.
+///
+/// The host bits of the address (those after the prefix length) are always zero. Prefixes
+/// order like a depth-first walk of the address space: IPv4 before IPv6, then by address,
+/// then shorter prefixes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IpPrefix {
+    addr: IpAddr,
+    len: u8,
+}
+
+/// Error returned for invalid IP prefixes, or when an [`IpPrefixTable`] cannot be updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpPrefixError {
+    /// A prefix string is not of the form `address[/length]`.
+    InvalidPrefix(String),
+    /// The prefix length exceeds the number of bits of the address (32 or 128).
+    InvalidLength(u8),
+    /// The underlying tree rejected the insertion.
+    Tree(i32),
+}
+
+impl fmt::Display for IpPrefixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpPrefixError::InvalidPrefix(prefix) => write!(f, "invalid IP prefix {:?}", prefix),
+            IpPrefixError::InvalidLength(len) => write!(f, "invalid IP prefix length {}", len),
+            IpPrefixError::Tree(rc) => write!(f, "radix tree insertion failed with error {}", rc),
+        }
+    }
+}
+
+impl std::error::Error for IpPrefixError {}
+
+/// Returns the address as an integer, with its number of bits.
+fn bits(addr: IpAddr) -> (u128, u8) {
+    match addr {
+        IpAddr::V4(addr) => (u32::from(addr) as u128, 32),
+        IpAddr::V6(addr) => (u128::from(addr), 128),
+    }
+}
+
+/// Clears the bits of `value` after the first `len` of `max`.
+fn mask(value: u128, len: u8, max: u8) -> u128 {
+    if len == 0 {
+        0
+    } else {
+        value & (!0u128 << (max - len))
+    }
+}
+
+fn with_bits(addr: IpAddr, value: u128) -> IpAddr {
+    match addr {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(value as u32)),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(value)),
+    }
+}
+
+/// Returns the tree key of the first `len` bits of `addr`.
+fn key(addr: IpAddr, len: u8) -> Vec<u8> {
+    let (value, max) = bits(addr);
+    let family = if addr.is_ipv4() { FAMILY_V4 } else { FAMILY_V6 };
+    let mut key = Vec::with_capacity(len as usize + 1);
+    key.push(family);
+    key.extend((0..len).map(|i| (value >> (max - 1 - i)) as u8 & 1));
+    key
+}
+
+impl IpPrefix {
+    /// Creates the prefix of the first `len` bits of `addr`, clearing its host bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpPrefixError::InvalidLength`] if `len` exceeds 32 for IPv4 or 128 for IPv6.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::IpPrefix;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let prefix = IpPrefix::new(Ipv4Addr::new(10, 1, 2, 3).into(), 8).unwrap();
+    /// assert_eq!(prefix.to_string(), "10.0.0.0/8");
+    /// assert!(IpPrefix::new(Ipv4Addr::LOCALHOST.into(), 33).is_err());
+    /// ```
+    pub fn new(addr: IpAddr, len: u8) -> Result<Self, IpPrefixError> {
+        let (value, max) = bits(addr);
+        if len > max {
+            return Err(IpPrefixError::InvalidLength(len));
+        }
+        Ok(IpPrefix {
+            addr: with_bits(addr, mask(value, len, max)),
+            len,
+        })
+    }
+
+    /// Returns the network address of the prefix.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the prefix, in bits.
+    pub fn prefix_len(&self) -> u8 {
+        self.len
+    }
+
+    /// Returns `true` if `addr` belongs to the prefix.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let (value, max) = bits(addr);
+        addr.is_ipv4() == self.addr.is_ipv4() && mask(value, self.len, max) == bits(self.addr).0
+    }
+
+    /// Returns `true` if `other` is equal to or more specific than this prefix.
+    pub fn covers(&self, other: &IpPrefix) -> bool {
+        self.len <= other.len && self.contains(other.addr)
+    }
+
+    /// Returns the prefix one bit shorter, or `None` for a zero-length prefix.
+    fn parent(&self) -> Option<IpPrefix> {
+        let len = self.len.checked_sub(1)?;
+        Some(IpPrefix::new(self.addr, len).unwrap())
+    }
+
+    /// Returns the other half of the parent prefix, or `None` for a zero-length prefix.
+    fn sibling(&self) -> Option<IpPrefix> {
+        if self.len == 0 {
+            return None;
+        }
+        let (value, max) = bits(self.addr);
+        Some(IpPrefix {
+            addr: with_bits(self.addr, value ^ (1 << (max - self.len))),
+            len: self.len,
+        })
+    }
+
+    fn key(&self) -> Vec<u8> {
+        key(self.addr, self.len)
+    }
+}
+
+/// A bare address is parsed as a full-length prefix.
+impl FromStr for IpPrefix {
+    type Err = IpPrefixError;
+
+    fn from_str(s: &str) -> Result<Self, IpPrefixError> {
+        let invalid = || IpPrefixError::InvalidPrefix(s.to_string());
+        let (addr, len) = match s.split_once('/') {
+            Some((addr, len)) => {
+                // Reject signs and leading zeros that u8::from_str would accept.
+                if len.is_empty()
+                    || len.len() > 3
+                    || (len.len() > 1 && len.starts_with('0'))
+                    || !len.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return Err(invalid());
+                }
+                (addr, Some(len.parse::<u8>().map_err(|_| invalid())?))
+            }
+            None => (s, None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| invalid())?;
+        IpPrefix::new(addr, len.unwrap_or(bits(addr).1))
+    }
+}
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+impl From<IpAddr> for IpPrefix {
+    fn from(addr: IpAddr) -> Self {
+        IpPrefix::new(addr, bits(addr).1).unwrap()
+    }
+}
+
+/// A longest-prefix-match table mapping IPv4 and IPv6 prefixes to values.
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::IpPrefixTable;
+///
+/// let mut table = IpPrefixTable::new().unwrap();
+/// table.insert("10.0.0.0/8".parse().unwrap(), "deny").unwrap();
+/// table.insert("10.1.0.0/16".parse().unwrap(), "allow").unwrap();
+/// table.insert("2001:db8::/32".parse().unwrap(), "allow").unwrap();
+///
+/// let (prefix, action) = table.longest_match("10.1.2.3".parse().unwrap()).unwrap();
+/// assert_eq!((prefix.to_string().as_str(), *action), ("10.1.0.0/16", "allow"));
+/// assert_eq!(table.longest_match("10.2.0.1".parse().unwrap()).map(|(_, a)| *a), Some("deny"));
+/// assert_eq!(table.longest_match("192.168.0.1".parse().unwrap()), None);
+/// ```
+pub struct IpPrefixTable<V> {
+    tree: RadixTree,
+    entries: Slab<(IpPrefix, V)>,
+}
+
+impl<V> IpPrefixTable<V> {
+    /// Creates a new empty table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    pub fn new() -> Result<Self, &'static str> {
+        Ok(Self {
+            tree: RadixTree::new()?,
+            entries: Slab::new(),
+        })
+    }
+
+    /// Returns the number of prefixes in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the table holds no prefix.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a prefix, returning the value it previously had, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpPrefixError::Tree`] if the tree insertion fails (on allocation failure).
+    pub fn insert(&mut self, prefix: IpPrefix, value: V) -> Result<Option<V>, IpPrefixError> {
+        let key = prefix.key();
+        if let Some(slot) = self.tree.find_exact(&key) {
+            let entry = self.entries.get_mut(slot).unwrap();
+            return Ok(Some(std::mem::replace(&mut entry.1, value)));
+        }
+        self.tree
+            .insert(&key, self.entries.next_value())
+            .map_err(IpPrefixError::Tree)?;
+        self.entries.insert((prefix, value));
+        Ok(None)
+    }
+
+    /// Returns the value of a prefix.
+    pub fn get(&self, prefix: &IpPrefix) -> Option<&V> {
+        let slot = self.tree.find_exact(prefix.key())?;
+        self.entries.get(slot).map(|(_, value)| value)
+    }
+
+    /// Removes a prefix, returning its value.
+    pub fn remove(&mut self, prefix: &IpPrefix) -> Option<V> {
+        let key = prefix.key();
+        let slot = self.tree.find_exact(&key)?;
+        self.tree.remove(&key).ok()?;
+        self.entries.remove(slot).map(|(_, value)| value)
+    }
+
+    /// Calls `visit` for every prefix containing `addr`, from the shortest to the longest.
+    fn walk<'a>(&'a self, addr: IpAddr, mut visit: impl FnMut(&'a IpPrefix, &'a V)) {
+        let key = key(addr, bits(addr).1);
+        self.tree.walk_prefixes(&key, |_, slot| {
+            if let Some((prefix, value)) = self.entries.get(slot) {
+                visit(prefix, value);
+            }
+        });
+    }
+
+    /// Returns the most specific prefix containing `addr`, with its value.
+    ///
+    /// IPv4 addresses only match IPv4 prefixes, and IPv6 addresses (including IPv4-mapped
+    /// ones) only IPv6 prefixes.
+    pub fn longest_match(&self, addr: IpAddr) -> Option<(IpPrefix, &V)> {
+        let mut found = None;
+        self.walk(addr, |prefix, value| found = Some((*prefix, value)));
+        found
+    }
+
+    /// Returns all the prefixes containing `addr` with their values, from the most
+    /// specific to the least specific.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::IpPrefixTable;
+    ///
+    /// let mut table = IpPrefixTable::new().unwrap();
+    /// table.insert("0.0.0.0/0".parse().unwrap(), 0).unwrap();
+    /// table.insert("10.0.0.0/8".parse().unwrap(), 8).unwrap();
+    /// table.insert("10.0.0.0/9".parse().unwrap(), 9).unwrap();
+    ///
+    /// let matches: Vec<_> = table.all_matches("10.1.0.1".parse().unwrap()).into_iter().map(|(_, v)| *v).collect();
+    /// assert_eq!(matches, vec![9, 8, 0]);
+    /// ```
+    pub fn all_matches(&self, addr: IpAddr) -> Vec<(IpPrefix, &V)> {
+        let mut matches = Vec::new();
+        self.walk(addr, |prefix, value| matches.push((*prefix, value)));
+        matches.reverse();
+        matches
+    }
+
+    /// Returns all the prefixes with their values, in [`IpPrefix`] order.
+    pub fn iter(&self) -> impl Iterator<Item = (IpPrefix, &V)> {
+        let mut entries: Vec<_> = self.entries.iter().map(|(prefix, value)| (*prefix, value)).collect();
+        entries.sort_by_key(|(prefix, _)| *prefix);
+        entries.into_iter()
+    }
+}
+
+impl<V: PartialEq> IpPrefixTable<V> {
+    /// Replaces the prefixes of the table with fewer prefixes giving the same longest
+    /// matches, returning the number of prefixes saved.
+    ///
+    /// Two adjacent halves of a prefix with equal values are merged into that prefix, and a
+    /// prefix is dropped when the closest less specific prefix has the same value. This is
+    /// repeated until no more prefixes can be merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpPrefixError::Tree`] if re-inserting the prefixes fails (on allocation
+    /// failure); the table may then have lost some of its prefixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::IpPrefixTable;
+    ///
+    /// let mut table = IpPrefixTable::new().unwrap();
+    /// for prefix in ["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/23", "10.0.3.0/24"] {
+    ///     table.insert(prefix.parse().unwrap(), "office").unwrap();
+    /// }
+    /// assert_eq!(table.aggregate().unwrap(), 3);
+    /// let prefixes: Vec<_> = table.iter().map(|(prefix, _)| prefix.to_string()).collect();
+    /// assert_eq!(prefixes, vec!["10.0.0.0/22"]);
+    /// ```
+    pub fn aggregate(&mut self) -> Result<usize, IpPrefixError> {
+        let tree = RadixTree::new().map_err(|_| IpPrefixError::Tree(-1))?;
+        let before = self.len();
+        let mut entries: BTreeMap<IpPrefix, V> = self.entries.drain().collect();
+        self.tree = tree;
+
+        while drop_redundant(&mut entries) | merge_siblings(&mut entries) {}
+
+        let after = entries.len();
+        for (prefix, value) in entries {
+            self.insert(prefix, value)?;
+        }
+        Ok(before - after)
+    }
+}
+
+/// Drops the prefixes whose closest less specific prefix has the same value.
+fn drop_redundant<V: PartialEq>(entries: &mut BTreeMap<IpPrefix, V>) -> bool {
+    let mut redundant = Vec::new();
+    // Less specific prefixes of the current one, in IpPrefix (depth-first) order.
+    let mut ancestors: Vec<&IpPrefix> = Vec::new();
+    for (prefix, value) in entries.iter() {
+        while ancestors.last().is_some_and(|ancestor| !ancestor.covers(prefix)) {
+            ancestors.pop();
+        }
+        match ancestors.last() {
+            Some(ancestor) if entries[*ancestor] == *value => redundant.push(*prefix),
+            _ => ancestors.push(prefix),
+        }
+    }
+    for prefix in &redundant {
+        entries.remove(prefix);
+    }
+    !redundant.is_empty()
+}
+
+/// Merges the pairs of sibling prefixes with equal values into their parent. The parent,
+/// if present, is entirely covered by its two halves and can be replaced.
+fn merge_siblings<V: PartialEq>(entries: &mut BTreeMap<IpPrefix, V>) -> bool {
+    let mut merged = false;
+    for len in (1..=128).rev() {
+        let candidates: Vec<IpPrefix> = entries.keys().filter(|prefix| prefix.len == len).copied().collect();
+        for prefix in candidates {
+            let sibling = prefix.sibling().unwrap();
+            // The pair may already have been merged when visiting the sibling.
+            if !entries.contains_key(&prefix) || entries.get(&sibling) != entries.get(&prefix) {
+                continue;
+            }
+            entries.remove(&sibling);
+            let value = entries.remove(&prefix).unwrap();
+            entries.insert(prefix.parent().unwrap(), value);
+            merged = true;
+        }
+    }
+    merged
+}
@@ -33,11 +33,15 @@
 //! assert_eq!(tree.find_exact("/api/users"), Some(2));
 //!
 //! // Longest prefix match_engine
-//! assert_eq!(tree.longest_prefix(&iter, "/api/users/123"), Some(2));
+//! let found = tree.longest_prefix(&iter, "/api/users/123").unwrap();
+//! assert_eq!(found.value, 2);
+//! assert_eq!(found.key, &b"/api/users"[..]);
+//! assert_eq!(found.remainder(), b"/123"); // The rest of the path, e.g. to forward upstream
 //!
 //! // Get all matching prefixes
 //! let matches = tree.find_all_prefixes(&iter, "/api/users/123/profile");
-//! assert_eq!(matches, vec![2, 1]); // ["/api/users", "/api"]
+//! let values: Vec<i32> = matches.iter().map(|m| m.value).collect();
+//! assert_eq!(values, vec![2, 1]); // ["/api/users", "/api"]
 //! ```
 //!
 //! ## Iterator-Style Matching
@@ -53,8 +57,8 @@
 //! let iter = tree.create_iter().unwrap();
 //! let path = "/api/v1/users";
 //! if tree.search(&iter, path) {
-//!     while let Some(idx) = tree.next_prefix(&iter, path) {
-//!         println!("Matched route: {}", idx);
+//!     while let Some(found) = tree.next_prefix(&iter, path) {
+//!         println!("Matched route {} with {} bytes left", found.value, found.remainder().len());
 //!     }
 //! }
 //! ```
@@ -68,22 +72,82 @@
 //! # Module Structure
 //!
 //! - `ffi`: Low-level FFI bindings to the C rax library
+//! - `alloc`: Allocator hooks routing the rax allocations to the Rust global allocator
 //! - `radix_tree`: High-level safe Rust API (`RadixTree` struct)
+//! - `case`: Case-insensitive key comparison (`CaseFolding`)
+//! - `complete`: Prefix completion, lexicographic or by key weight (`RadixTree::complete`)
+//! - `node`: Read-only access to the nodes of a tree
+//! - `dump`: Structure dumps for debugging (`RadixTree::debug_dump` / `RadixTree::to_dot`)
+//! - `flat`: Flattened read-only trees that can be memory-mapped (`FlatTree`)
+//! - `router`: Path, host and method based routing on top of the tree (`Router`)
+//! - `domain`: Domain name rule sets matching hosts by suffix (`DomainSet`)
+//! - `fuzzy`: Edit-distance lookups for "did you mean" suggestions (`RadixTree::fuzzy_find`)
+//! - `glob`: Glob pattern matching with `*`, `?` and `**` wildcards (`GlobRouter` / `RadixTree::find_matching`)
+//! - `ip_prefix`: Longest-prefix-match tables of IPv4/IPv6 CIDR prefixes (`IpPrefixTable`)
+//! - `topic`: MQTT topic filter matching with `+` and `#` wildcards (`TopicMatcher`)
+//! - `config`: Route-table loader for JSON/YAML/TOML files (requires the `json`, `yaml` or `toml` feature)
+//! - `reload`: Hot reloading of route-table files (`ReloadingRouter`, same features as `config`)
+//! - `serde_support`: `Serialize`/`Deserialize` for `RadixTree` (requires the `serde` feature)
+//! - `snapshot`: Binary snapshot serialization (`RadixTree::write_snapshot` / `RadixTree::read_snapshot`)
+//! - `stats`: Memory usage and shape statistics (`RadixTree::stats`)
 //! - `c_api`: C ABI exports for use from other languages
 
+mod alloc;
 mod c_api;
+mod case;
+mod complete;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+mod config;
+mod domain;
+mod dump;
 mod ffi;
+mod flat;
+mod fuzzy;
+mod glob;
+mod ip_prefix;
+mod node;
 mod radix_tree;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+mod reload;
+mod router;
+#[cfg(feature = "serde")]
+mod serde_support;
+mod slab;
+mod snapshot;
+mod stats;
+mod topic;
 
 #[cfg(test)]
 mod tests;
 
 // Re-export the main public API
-pub use radix_tree::RadixTree;
+pub use case::CaseFolding;
+pub use domain::{DomainError, DomainSet, ListFormat};
+pub use dump::{DumpNode, NodeKind};
+pub use glob::{GlobError, GlobRouter};
+pub use ip_prefix::{IpPrefix, IpPrefixError, IpPrefixTable};
+pub use radix_tree::{Iter, Match, RadixIterator, RadixTree};
+pub use router::{Route, RouteError, Router};
+pub use stats::{MemoryUsage, TreeStats};
+pub use topic::{TopicError, TopicMatcher};
+
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+pub use config::{ConfigError, ConfigErrorKind, ConfigFormat, Location, RouteConfig};
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+pub use reload::{ReloadStatus, ReloadingRouter, ReloadingRouterBuilder};
+
+pub use flat::FlatTree;
+#[cfg(unix)]
+pub use flat::Mmap;
 
 // Re-export C API functions for documentation purposes
 pub use c_api::{
-    radix_tree_destroy, radix_tree_find, radix_tree_insert, radix_tree_new, radix_tree_new_it, radix_tree_remove,
-    radix_tree_search, radix_tree_up,
+    radix_iter_t, radix_tree_abi_version, radix_tree_allocated_bytes, radix_tree_destroy, radix_tree_features,
+    radix_tree_find, radix_tree_foreach, radix_tree_foreach_cb, radix_tree_foreach_prefix, radix_tree_free_it,
+    radix_tree_insert, radix_tree_insert_many, radix_tree_last_error, radix_tree_match_many, radix_tree_new,
+    radix_tree_new_it, radix_tree_remove, radix_tree_search, radix_tree_t, radix_tree_up, radix_tree_version_string,
+    RADIX_TREE_ABI_VERSION, RADIX_TREE_EBADHANDLE, RADIX_TREE_EINVAL, RADIX_TREE_EPANIC,
+    RADIX_TREE_FEATURE_HANDLE_CHECKS, RADIX_TREE_FEATURE_IDNA, RADIX_TREE_FEATURE_JSON, RADIX_TREE_FEATURE_SERDE,
+    RADIX_TREE_FEATURE_TOML, RADIX_TREE_FEATURE_YAML,
 };
 
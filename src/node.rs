@@ -0,0 +1,186 @@
+//! Read-only view of the nodes of a rax tree
+//!
+//! This model wraps the low level node accessors exported by `rax.c`, so that the
+//! tree structure can be walked directly, without going through a `raxIterator`.
+
+use crate::alloc::block_size;
+use crate::ffi::*;
+use crate::radix_tree::RadixTree;
+use std::marker::PhantomData;
+use std::slice;
+
+/// A borrowed reference to a node of a rax tree.
+///
+/// The lifetime ties the node to the tree it belongs to: nodes may be reallocated
+/// by any modification of the tree, so a `Node` must not outlive a shared borrow.
+#[derive(Clone, Copy)]
+pub(crate) struct Node<'a> {
+    ptr: *mut RaxNode,
+    _tree: PhantomData<&'a Rax>,
+}
+
+impl<'a> Node<'a> {
+    /// Returns the head node of the tree.
+    ///
+    /// # Safety
+    ///
+    /// `rax` must be a valid tree that is not modified for the lifetime `'a`.
+    pub(crate) unsafe fn head(rax: *const Rax) -> Node<'a> {
+        Node {
+            ptr: (*rax).head,
+            _tree: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the node terminates a key.
+    pub(crate) fn is_key(&self) -> bool {
+        unsafe { raxNodeIsKey(self.ptr) != 0 }
+    }
+
+    /// Returns `true` if the node is a key without an associated value.
+    pub(crate) fn is_null(&self) -> bool {
+        unsafe { raxNodeIsNull(self.ptr) != 0 }
+    }
+
+    /// Returns `true` if the node is compressed (a chain of single-child nodes).
+    pub(crate) fn is_compressed(&self) -> bool {
+        unsafe { raxNodeIsCompr(self.ptr) != 0 }
+    }
+
+    /// Returns the node characters.
+    ///
+    /// For compressed nodes this is the string leading to the only child, otherwise
+    /// it holds one edge byte per child, in sorted order.
+    pub(crate) fn chars(&self) -> &'a [u8] {
+        unsafe {
+            let size = raxNodeSize(self.ptr);
+            if size == 0 {
+                return &[];
+            }
+            slice::from_raw_parts(raxNodeChars(self.ptr), size)
+        }
+    }
+
+    /// Returns the raw value pointer stored in a key node, if any.
+    pub(crate) fn data(&self) -> Option<*mut libc::c_void> {
+        if !self.is_key() || self.is_null() {
+            return None;
+        }
+        Some(unsafe { raxGetData(self.ptr) })
+    }
+
+    /// Returns an identifier of the node, valid until the tree is modified.
+    pub(crate) fn id(&self) -> usize {
+        self.ptr as usize
+    }
+
+    /// Returns the number of bytes allocated for the node.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        unsafe { block_size(self.ptr as *const libc::c_void) }
+    }
+
+    /// Returns the number of children of the node.
+    pub(crate) fn child_count(&self) -> usize {
+        if self.is_compressed() {
+            1
+        } else {
+            unsafe { raxNodeSize(self.ptr) }
+        }
+    }
+
+    /// Returns the i-th child of the node.
+    pub(crate) fn child(&self, i: usize) -> Node<'a> {
+        debug_assert!(i < self.child_count());
+        Node {
+            ptr: unsafe { raxNodeChild(self.ptr, i) },
+            _tree: PhantomData,
+        }
+    }
+}
+
+/// A position in a rax tree, between two bytes of a key: at the start of `node`, or
+/// `offset` bytes into the string of a compressed node.
+#[derive(Clone, Copy)]
+pub(crate) struct Cursor<'a> {
+    node: Node<'a>,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Returns a cursor at the head of the tree, before the first byte of any key.
+    pub(crate) fn new(tree: &'a RadixTree) -> Cursor<'a> {
+        Cursor {
+            node: unsafe { Node::head(tree.tree as *const Rax) },
+            offset: 0,
+        }
+    }
+
+    /// Returns the node at the start of which the cursor is, if it is not inside the string
+    /// of a compressed node.
+    pub(crate) fn node(&self) -> Option<Node<'a>> {
+        (self.offset == 0).then_some(self.node)
+    }
+
+    /// Returns the first node at or below the cursor, with the bytes leading to it.
+    pub(crate) fn descend(&self) -> (Node<'a>, &'a [u8]) {
+        if self.offset == 0 {
+            (self.node, &[])
+        } else {
+            (self.node.child(0), &self.node.chars()[self.offset..])
+        }
+    }
+
+    /// Returns the value of the key ending at the cursor, if any.
+    pub(crate) fn value(&self) -> Option<i32> {
+        if self.offset > 0 {
+            return None;
+        }
+        self.node.data().map(|data| data as isize as i32)
+    }
+
+    /// Moves past `byte`, if a key continues with it.
+    pub(crate) fn step(&self, byte: u8) -> Option<Cursor<'a>> {
+        let chars = self.node.chars();
+        if self.node.is_compressed() {
+            if chars.get(self.offset) != Some(&byte) {
+                return None;
+            }
+            if self.offset + 1 < chars.len() {
+                return Some(Cursor {
+                    offset: self.offset + 1,
+                    ..*self
+                });
+            }
+            Some(Cursor {
+                node: self.node.child(0),
+                offset: 0,
+            })
+        } else {
+            let i = chars.binary_search(&byte).ok()?;
+            Some(Cursor {
+                node: self.node.child(i),
+                offset: 0,
+            })
+        }
+    }
+
+    /// Returns the bytes that keys continue with after the cursor, in sorted order, each
+    /// with the cursor past it.
+    pub(crate) fn children(&self) -> impl Iterator<Item = (u8, Cursor<'a>)> + 'a {
+        let cursor = *self;
+        let chars = self.node.chars();
+        let bytes = if self.node.is_compressed() {
+            &chars[self.offset.min(chars.len())..(self.offset + 1).min(chars.len())]
+        } else {
+            chars
+        };
+        bytes
+            .iter()
+            .filter_map(move |&byte| cursor.step(byte).map(|next| (byte, next)))
+    }
+
+    /// Moves past `bytes`, if a key continues with them.
+    pub(crate) fn walk(&self, bytes: &[u8]) -> Option<Cursor<'a>> {
+        bytes.iter().try_fold(*self, |cursor, &byte| cursor.step(byte))
+    }
+}
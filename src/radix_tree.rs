@@ -2,15 +2,25 @@
 //!
 //! This model provides a safe, idiomatic Rust interface to the underlying C implementation.
 
+use crate::alloc::charge_to;
+use crate::case::CaseFolding;
+use crate::complete::Weights;
 use crate::ffi::*;
+use crate::node::Node;
 use libc::c_void;
-use std::ptr;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{ptr, slice};
 
 /// A high-level Rust wrapper for the Radix Tree data structure.
 ///
 /// `RadixTree` provides efficient storage and retrieval of string keys with associated
 /// integer values. It supports exact matching, prefix matching, and iteration.
 ///
+/// Keys are binary-safe: every method taking a key accepts any `AsRef<[u8]>`, such as
+/// `&str`, `String`, `&[u8]` or `Vec<u8>`, so non UTF-8 keys can be stored too.
+///
 /// # Examples
 ///
 /// ```
@@ -23,10 +33,18 @@ use std::ptr;
 /// assert_eq!(tree.find_exact("/api/users"), Some(1));
 ///
 /// let iter = tree.create_iter().unwrap();
-/// assert_eq!(tree.longest_prefix(&iter, "/api/users/123"), Some(1));
+/// let found = tree.longest_prefix(&iter, "/api/users/123").unwrap();
+/// assert_eq!((found.value, found.remainder()), (1, &b"/123"[..]));
 /// ```
 pub struct RadixTree {
-    tree: *mut c_void,
+    pub(crate) tree: *mut c_void,
+    /// Bytes allocated for the nodes of the tree. Boxed, since the nodes point to it.
+    allocated: Box<AtomicUsize>,
+    /// Key weights for `complete_by_weight()`.
+    pub(crate) weights: Weights,
+    folding: CaseFolding,
+    /// Spelling of the keys inserted in a form other than their folded form.
+    spellings: HashMap<Box<[u8]>, Box<[u8]>>,
 }
 
 /// Iterator for RadixTree operations.
@@ -42,6 +60,55 @@ pub struct RadixIterator {
     iter: *mut c_void,
 }
 
+/// A key of a tree that is a prefix of a path, returned by the prefix queries of
+/// [`RadixTree`] and [`FlatTree`](crate::FlatTree).
+///
+/// The match borrows the path, so that the rest of the path after the key, such as the
+/// part of a URL to forward upstream, is available as a sub-slice of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'p> {
+    /// The value of the key.
+    pub value: i32,
+    /// Length of the matched prefix of the path in bytes. This is the length of the key,
+    /// unless a case-insensitive tree matched a character folding to one of another
+    /// length, such as the Kelvin sign to `k`.
+    pub matched_len: usize,
+    /// The key, as it was inserted. It borrows the matched prefix of the path when that is
+    /// spelled as the key, as it always is in a case-sensitive tree, so that matching does
+    /// not allocate.
+    pub key: Cow<'p, [u8]>,
+    path: &'p [u8],
+}
+
+impl<'p> Match<'p> {
+    pub(crate) fn new(value: i32, key: Cow<'p, [u8]>, matched_len: usize, path: &'p [u8]) -> Self {
+        Match {
+            value,
+            matched_len,
+            key,
+            path,
+        }
+    }
+
+    /// Returns the matched prefix of the path, spelled as in the path.
+    pub fn matched(&self) -> &'p [u8] {
+        &self.path[..self.matched_len]
+    }
+
+    /// Returns the rest of the path after the matched prefix.
+    pub fn remainder(&self) -> &'p [u8] {
+        &self.path[self.matched_len..]
+    }
+}
+
+/// Iterator over the keys and values of a RadixTree, in lexicographic byte order.
+///
+/// Created by [`RadixTree::iter`].
+pub struct Iter<'a> {
+    iter: RadixIterator,
+    tree: &'a RadixTree,
+}
+
 impl RadixTree {
     /// Creates a new empty Radix Tree.
     ///
@@ -57,12 +124,49 @@ impl RadixTree {
     /// let tree = RadixTree::new().unwrap();
     /// ```
     pub fn new() -> Result<Self, &'static str> {
-        let tree = unsafe { tree_new_raw() };
+        Self::with_case_folding(CaseFolding::Sensitive)
+    }
+
+    /// Creates a new empty Radix Tree comparing keys as set by `folding`.
+    ///
+    /// A case-insensitive tree folds keys when they are inserted, and paths when they are
+    /// looked up, while [`iter()`](Self::iter) and the other methods returning keys give them
+    /// back as they were last inserted. [`debug_dump()`](Self::debug_dump) node strings hold
+    /// the folded keys. Snapshots keep the case folding and spellings of the tree, flattened
+    /// trees keep only the case folding, while serializing a case-insensitive tree with
+    /// serde fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::{CaseFolding, RadixTree};
+    ///
+    /// let mut tree = RadixTree::with_case_folding(CaseFolding::Ascii).unwrap();
+    /// tree.insert("/api/Users", 1).unwrap();
+    ///
+    /// assert_eq!(tree.find_exact("/API/USERS"), Some(1));
+    /// let iter = tree.create_iter().unwrap();
+    /// assert_eq!(tree.longest_prefix(&iter, "/Api/users/42").unwrap().key, &b"/api/Users"[..]);
+    /// assert_eq!(tree.iter().unwrap().next(), Some((b"/api/Users".to_vec(), 1)));
+    /// ```
+    pub fn with_case_folding(folding: CaseFolding) -> Result<Self, &'static str> {
+        let allocated = Box::new(AtomicUsize::new(0));
+        let tree = charge_to(&allocated, || unsafe { tree_new_raw() });
         if tree.is_null() {
             return Err("failed to allocate radix tree");
         }
 
-        Ok(Self { tree })
+        Ok(Self {
+            tree,
+            allocated,
+            weights: Weights::default(),
+            folding,
+            spellings: HashMap::new(),
+        })
     }
 
     /// Creates a new iterator for this tree.
@@ -96,7 +200,7 @@ impl RadixTree {
     ///
     /// # Arguments
     ///
-    /// * `path` - The path string to insert
+    /// * `path` - The path to insert, as a string or bytes
     /// * `idx` - The integer index to associate with this path (must be > 0)
     ///
     /// # Errors
@@ -111,15 +215,28 @@ impl RadixTree {
     /// let mut tree = RadixTree::new().unwrap();
     /// tree.insert("/api", 1).unwrap();
     /// tree.insert("/api/users", 2).unwrap();
+    /// // Keys need not be UTF-8.
+    /// tree.insert(b"\x00\x01\xff", 3).unwrap();
+    /// assert_eq!(tree.find_exact([0x00, 0x01, 0xff]), Some(3));
     /// ```
-    pub fn insert(&mut self, path: &str, idx: i32) -> Result<(), i32> {
-        let bytes = path.as_bytes();
-        let rc = unsafe { tree_insert_raw(self.tree, bytes.as_ptr(), bytes.len(), idx) };
+    pub fn insert<K: AsRef<[u8]>>(&mut self, path: K, idx: i32) -> Result<(), i32> {
+        let original = path.as_ref();
+        let bytes = self.folding.fold(original);
+        let rc = self.charged(|tree| unsafe { tree_insert_raw(tree, bytes.as_ptr(), bytes.len(), idx) });
+        self.weights.invalidate();
         if rc < 0 {
-            Err(rc)
-        } else {
-            Ok(())
+            return Err(rc);
+        }
+        match bytes {
+            Cow::Owned(folded) => {
+                self.spellings.insert(folded.into(), original.into());
+            }
+            Cow::Borrowed(_) if !self.spellings.is_empty() => {
+                self.spellings.remove(original);
+            }
+            Cow::Borrowed(_) => {}
         }
+        Ok(())
     }
 
     /// Finds the exact match_engine for a path.
@@ -143,8 +260,8 @@ impl RadixTree {
     /// assert_eq!(tree.find_exact("/api"), Some(1));
     /// assert_eq!(tree.find_exact("/api/users"), None);
     /// ```
-    pub fn find_exact(&self, path: &str) -> Option<i32> {
-        let bytes = path.as_bytes();
+    pub fn find_exact<K: AsRef<[u8]>>(&self, path: K) -> Option<i32> {
+        let bytes = self.fold(path.as_ref());
         let res = unsafe { tree_find_raw(self.tree, bytes.as_ptr(), bytes.len()) };
         if res.is_null() {
             None
@@ -173,12 +290,14 @@ impl RadixTree {
     /// tree.remove("/api").unwrap();
     /// assert_eq!(tree.find_exact("/api"), None);
     /// ```
-    pub fn remove(&mut self, path: &str) -> Result<(), i32> {
-        let bytes = path.as_bytes();
-        let rc = unsafe { tree_remove_raw(self.tree, bytes.as_ptr(), bytes.len()) };
+    pub fn remove<K: AsRef<[u8]>>(&mut self, path: K) -> Result<(), i32> {
+        let bytes = self.folding.fold(path.as_ref());
+        let rc = self.charged(|tree| unsafe { tree_remove_raw(tree, bytes.as_ptr(), bytes.len()) });
         if rc < 0 {
             Err(rc)
         } else {
+            self.weights.remove(&bytes);
+            self.spellings.remove(&*bytes);
             Ok(())
         }
     }
@@ -195,7 +314,7 @@ impl RadixTree {
     ///
     /// # Returns
     ///
-    /// Returns `Some(idx)` of the longest matching prefix, `None` if no match_engine.
+    /// Returns the [`Match`] of the longest matching prefix, `None` if no match_engine.
     ///
     /// # Examples
     ///
@@ -208,24 +327,36 @@ impl RadixTree {
     ///
     /// let iter = tree.create_iter().unwrap();
     /// // Matches "/api/users" (idx=2)
-    /// assert_eq!(tree.longest_prefix(&iter, "/api/users/123"), Some(2));
+    /// let found = tree.longest_prefix(&iter, "/api/users/123").unwrap();
+    /// assert_eq!(found.value, 2);
+    /// assert_eq!(found.matched_len, 10);
+    /// assert_eq!(found.remainder(), b"/123");
     /// ```
-    pub fn longest_prefix(&self, iter: &RadixIterator, path: &str) -> Option<i32> {
-        let bytes = path.as_bytes();
-        let ptr = bytes.as_ptr();
-        let len = bytes.len();
-
-        let search_ptr = unsafe { tree_search_raw(self.tree, iter.iter, ptr, len) };
-        if search_ptr.is_null() {
-            return None;
-        }
-
-        let idx = unsafe { tree_up_raw(iter.iter, ptr, len) };
+    pub fn longest_prefix<'p, K: AsRef<[u8]> + ?Sized>(&self, iter: &RadixIterator, path: &'p K) -> Option<Match<'p>> {
+        let path = path.as_ref();
+        let bytes = self.fold(path);
+        let idx = unsafe { tree_longest_prefix_raw(self.tree, iter.iter, bytes.as_ptr(), bytes.len()) };
         if idx <= 0 {
             None
         } else {
-            Some(idx)
+            Some(self.iter_match(iter, path, idx))
+        }
+    }
+
+    /// Returns the match of `path` whose key `iter` was left on, with its value.
+    fn iter_match<'p>(&self, iter: &RadixIterator, path: &'p [u8], value: i32) -> Match<'p> {
+        let it = iter.iter as *const RaxIterator;
+        let key = unsafe { slice::from_raw_parts((*it).key, (*it).key_len) };
+        if self.folding == CaseFolding::Sensitive {
+            return Match::new(value, Cow::Borrowed(&path[..key.len()]), key.len(), path);
         }
+        let matched_len = self.folding.original_len(path, key.len());
+        let key = match self.spellings.get(key) {
+            Some(spelling) => Cow::Owned(spelling.to_vec()),
+            None if path[..matched_len] == *key => Cow::Borrowed(&path[..matched_len]),
+            None => Cow::Owned(key.to_vec()),
+        };
+        Match::new(value, key, matched_len, path)
     }
 
     /// Initializes the iterator for prefix searching.
@@ -251,13 +382,13 @@ impl RadixTree {
     ///
     /// let iter = tree.create_iter().unwrap();
     /// if tree.search(&iter, "/api/users") {
-    ///     while let Some(idx) = tree.next_prefix(&iter, "/api/users") {
-    ///         println!("Matched: {}", idx);
+    ///     while let Some(found) = tree.next_prefix(&iter, "/api/users") {
+    ///         println!("Matched: {}", found.value);
     ///     }
     /// }
     /// ```
-    pub fn search(&self, iter: &RadixIterator, path: &str) -> bool {
-        let bytes = path.as_bytes();
+    pub fn search<K: AsRef<[u8]>>(&self, iter: &RadixIterator, path: K) -> bool {
+        let bytes = self.fold(path.as_ref());
         let search_ptr = unsafe { tree_search_raw(self.tree, iter.iter, bytes.as_ptr(), bytes.len()) };
         !search_ptr.is_null()
     }
@@ -273,18 +404,19 @@ impl RadixTree {
     ///
     /// # Returns
     ///
-    /// Returns `Some(idx)` for the next match_engine, `None` when no more matches.
+    /// Returns the [`Match`] of the next prefix, `None` when no more matches.
     ///
     /// # Examples
     ///
     /// See `search()` for example usage.
-    pub fn next_prefix(&self, iter: &RadixIterator, path: &str) -> Option<i32> {
-        let bytes = path.as_bytes();
+    pub fn next_prefix<'p, K: AsRef<[u8]> + ?Sized>(&self, iter: &RadixIterator, path: &'p K) -> Option<Match<'p>> {
+        let path = path.as_ref();
+        let bytes = self.fold(path);
         let idx = unsafe { tree_up_raw(iter.iter, bytes.as_ptr(), bytes.len()) };
         if idx <= 0 {
             None
         } else {
-            Some(idx)
+            Some(self.iter_match(iter, path, idx))
         }
     }
 
@@ -300,7 +432,7 @@ impl RadixTree {
     ///
     /// # Returns
     ///
-    /// A vector of indices for all matching prefixes, from longest to shortest.
+    /// A vector of [`Match`]es for all matching prefixes, from longest to shortest.
     ///
     /// # Examples
     ///
@@ -314,21 +446,296 @@ impl RadixTree {
     ///
     /// let iter = tree.create_iter().unwrap();
     /// let matches = tree.find_all_prefixes(&iter, "/api/users/123");
-    /// assert_eq!(matches, vec![3, 2, 1]);
+    /// let values: Vec<i32> = matches.iter().map(|m| m.value).collect();
+    /// assert_eq!(values, vec![3, 2, 1]);
+    /// assert_eq!(matches[1].remainder(), b"/users/123");
     /// ```
-    pub fn find_all_prefixes(&self, iter: &RadixIterator, path: &str) -> Vec<i32> {
+    pub fn find_all_prefixes<'p, K: AsRef<[u8]> + ?Sized>(&self, iter: &RadixIterator, path: &'p K) -> Vec<Match<'p>> {
+        let path = path.as_ref();
+        // Folded once, rather than by every step of the walk.
+        let bytes = &*self.fold(path);
         let mut results = Vec::with_capacity(10);
 
-        if !self.search(iter, path) {
+        if !self.search(iter, bytes) {
             return results;
         }
 
-        while let Some(idx) = self.next_prefix(iter, path) {
-            results.push(idx);
+        loop {
+            let idx = unsafe { tree_up_raw(iter.iter, bytes.as_ptr(), bytes.len()) };
+            if idx <= 0 {
+                break;
+            }
+            results.push(self.iter_match(iter, path, idx));
         }
 
         results
     }
+
+    /// Inserts several paths at once.
+    ///
+    /// This is equivalent to calling `insert()` for each entry, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The paths to insert, with their indices (must be > 0)
+    ///
+    /// # Errors
+    ///
+    /// Returns the error code of the first failed insertion. Entries before it are inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api", 1), ("/api/users", 2)]).unwrap();
+    /// assert_eq!(tree.find_exact("/api/users"), Some(2));
+    /// ```
+    pub fn insert_many<K, I>(&mut self, entries: I) -> Result<(), i32>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = (K, i32)>,
+    {
+        entries.into_iter().try_for_each(|(path, idx)| self.insert(path, idx))
+    }
+
+    /// Finds the longest prefix match_engine for each of several paths.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - A RadixIterator for this tree
+    /// * `paths` - The paths to match_engine
+    /// * `results` - Receives, for each path, the [`Match`] of its longest matching prefix
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of paths that matched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paths` and `results` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api", 1), ("/api/users", 2)]).unwrap();
+    ///
+    /// let iter = tree.create_iter().unwrap();
+    /// let mut results = vec![None; 3];
+    /// let matched = tree.match_many(&iter, &["/api/users/1", "/api/posts", "/static"], &mut results);
+    /// assert_eq!(matched, 2);
+    /// let values: Vec<_> = results.iter().map(|m| m.as_ref().map(|m| m.value)).collect();
+    /// assert_eq!(values, [Some(2), Some(1), None]);
+    /// ```
+    pub fn match_many<'p, K: AsRef<[u8]>>(
+        &self,
+        iter: &RadixIterator,
+        paths: &'p [K],
+        results: &mut [Option<Match<'p>>],
+    ) -> usize {
+        assert_eq!(
+            paths.len(),
+            results.len(),
+            "paths and results must have the same length"
+        );
+        let mut matched = 0;
+        for (path, result) in paths.iter().zip(results.iter_mut()) {
+            *result = self.longest_prefix(iter, path);
+            matched += result.is_some() as usize;
+        }
+        matched
+    }
+}
+
+impl RadixTree {
+    /// Returns the number of keys in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// assert!(tree.is_empty());
+    /// tree.insert_many([("/api", 1), ("/api/users", 2)]).unwrap();
+    /// assert_eq!(tree.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        unsafe { (*(self.tree as *const Rax)).numele as usize }
+    }
+
+    /// Returns `true` if the tree holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of nodes of the tree, including its head node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// assert_eq!(tree.node_count(), 1);
+    /// tree.insert("/api", 1).unwrap();
+    /// assert_eq!(tree.node_count(), 2);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        unsafe { (*(self.tree as *const Rax)).numnodes as usize }
+    }
+
+    /// Returns the number of bytes currently allocated for the nodes of the tree.
+    ///
+    /// Tree memory comes from the Rust global allocator; this counts the bytes requested
+    /// by the tree. Each node allocation also carries a 16-byte header, and the
+    /// bookkeeping overhead of the allocator, which are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// let empty = tree.allocated_bytes();
+    /// tree.insert("/api/users", 1).unwrap();
+    /// tree.insert("/api/posts", 2).unwrap();
+    /// let full = tree.allocated_bytes();
+    /// assert!(full > empty);
+    /// tree.remove("/api/posts").unwrap();
+    /// assert!(tree.allocated_bytes() < full);
+    /// ```
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated.load(Ordering::Relaxed)
+    }
+
+    /// Walks `path` down from the head node, calling `visit(matched_len, idx)` for every key
+    /// with a value that is a prefix of `path`, from the shortest to the longest.
+    ///
+    /// Unlike `find_all_prefixes()` this needs no iterator and never allocates.
+    pub(crate) fn walk_prefixes(&self, original: &[u8], mut visit: impl FnMut(usize, i32)) {
+        let path = &*self.fold(original);
+        let mut node = unsafe { Node::head(self.tree as *const Rax) };
+        let mut pos = 0;
+        loop {
+            if let Some(data) = node.data() {
+                visit(self.folding.original_len(original, pos), data as isize as i32);
+            }
+            let chars = node.chars();
+            if chars.is_empty() {
+                return;
+            }
+            if node.is_compressed() {
+                if !path[pos..].starts_with(chars) {
+                    return;
+                }
+                pos += chars.len();
+                node = node.child(0);
+            } else {
+                let Some(byte) = path.get(pos) else {
+                    return;
+                };
+                let Ok(i) = chars.binary_search(byte) else {
+                    return;
+                };
+                pos += 1;
+                node = node.child(i);
+            }
+        }
+    }
+
+    /// Returns the comparison of keys set when the tree was created.
+    pub fn case_folding(&self) -> CaseFolding {
+        self.folding
+    }
+
+    /// Returns a key or path folded as the keys of the tree are.
+    pub(crate) fn fold<'k>(&self, key: &'k [u8]) -> Cow<'k, [u8]> {
+        self.folding.fold(key)
+    }
+
+    /// Returns the keys inserted in a form other than their folded form, as inserted.
+    pub(crate) fn spelled_keys(&self) -> impl Iterator<Item = &[u8]> {
+        self.spellings.values().map(|spelling| &**spelling)
+    }
+
+    /// Records `spelling` as the spelling of the key it folds to, as `insert()` does.
+    ///
+    /// Returns `false` if the folded key is not in the tree, or is spelled the same.
+    pub(crate) fn restore_spelling(&mut self, spelling: &[u8]) -> bool {
+        let Cow::Owned(folded) = self.folding.fold(spelling) else {
+            return false;
+        };
+        if self.find_exact(&folded).is_none() {
+            return false;
+        }
+        self.spellings.insert(folded.into(), spelling.into());
+        true
+    }
+
+    /// Returns a key of the tree spelled as it was inserted.
+    pub(crate) fn spelling(&self, key: Vec<u8>) -> Vec<u8> {
+        match self.spellings.get(&*key) {
+            Some(original) => original.to_vec(),
+            None => key,
+        }
+    }
+
+    /// Runs `f` on the raw tree, charging the memory it allocates to this tree.
+    pub(crate) fn charged<R>(&mut self, f: impl FnOnce(*mut c_void) -> R) -> R {
+        let tree = self.tree;
+        charge_to(&self.allocated, || f(tree))
+    }
+
+    /// Returns an iterator over all keys and their indices, in lexicographic byte order.
+    ///
+    /// Keys are returned as bytes, since the tree itself is binary-safe. A case-insensitive
+    /// tree orders keys by their folded form, and returns them as they were inserted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if iterator allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert("/b", 2).unwrap();
+    /// tree.insert("/a", 1).unwrap();
+    ///
+    /// let entries: Vec<_> = tree.iter().unwrap().collect();
+    /// assert_eq!(entries, vec![(b"/a".to_vec(), 1), (b"/b".to_vec(), 2)]);
+    /// ```
+    pub fn iter(&self) -> Result<Iter<'_>, &'static str> {
+        let iter = self.create_iter()?;
+        unsafe { tree_seek_first_raw(iter.iter) };
+        Ok(Iter { iter, tree: self })
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = (Vec<u8>, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let it = self.iter.iter as *mut RaxIterator;
+        loop {
+            if unsafe { tree_next_raw(self.iter.iter) } == 0 {
+                return None;
+            }
+            let (key, data) = unsafe { (slice::from_raw_parts((*it).key, (*it).key_len), (*it).data) };
+            // Keys inserted without a value are not visible through find_exact() either.
+            if data.is_null() {
+                continue;
+            }
+            return Some((self.tree.spelling(key.to_vec()), data as isize as i32));
+        }
+    }
 }
 
 impl Drop for RadixTree {
@@ -0,0 +1,364 @@
+//! Hot reloading of route-table files
+//!
+//! This model watches a route-table file (with inotify on Linux, by polling its metadata
+//! elsewhere or when inotify is unavailable), and atomically swaps the active [`Router`]
+//! whenever the file changes and still builds. An invalid file never replaces the last
+//! good router.
+
+use crate::config::{ConfigError, ConfigErrorKind, RouteConfig};
+use crate::router::Router;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// Outcome of a reload attempt, passed to the reload callback.
+#[derive(Debug)]
+pub enum ReloadStatus {
+    /// The file was reloaded and the new router is now active.
+    Reloaded {
+        /// Number of routes in the new router.
+        routes: usize,
+    },
+    /// The file could not be loaded; the previous router stays active.
+    Failed(ConfigError),
+}
+
+type ReloadCallback = Box<dyn Fn(&ReloadStatus) + Send + Sync>;
+
+struct Shared {
+    path: PathBuf,
+    current: RwLock<Arc<Router>>,
+    on_reload: Option<ReloadCallback>,
+    reloading: Mutex<()>,
+    stop: AtomicBool,
+    wakeup: (Mutex<()>, Condvar),
+}
+
+impl Shared {
+    fn reload(&self) -> Result<(), ConfigError> {
+        // Serialize reloads, so that a slow load never overwrites a more recent one.
+        let _reloading = self.reloading.lock().unwrap_or_else(|e| e.into_inner());
+        let result = RouteConfig::load(&self.path).and_then(|config| config.build());
+        let status = match result {
+            Ok(router) => {
+                let routes = router.routes().len();
+                *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(router);
+                ReloadStatus::Reloaded { routes }
+            }
+            Err(err) => ReloadStatus::Failed(err),
+        };
+        if let Some(callback) = &self.on_reload {
+            callback(&status);
+        }
+        match status {
+            ReloadStatus::Reloaded { .. } => Ok(()),
+            ReloadStatus::Failed(err) => Err(err),
+        }
+    }
+
+    /// Sleeps for `timeout`, returning early (with `true`) when the watcher is stopped.
+    fn wait(&self, timeout: Duration) -> bool {
+        let (lock, cvar) = &self.wakeup;
+        let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        if self.stop.load(Ordering::Acquire) {
+            return true;
+        }
+        let _ = cvar.wait_timeout(guard, timeout);
+        self.stop.load(Ordering::Acquire)
+    }
+}
+
+/// Builder for [`ReloadingRouter`].
+pub struct ReloadingRouterBuilder {
+    path: PathBuf,
+    poll_interval: Duration,
+    polling: bool,
+    on_reload: Option<ReloadCallback>,
+}
+
+impl ReloadingRouterBuilder {
+    /// Sets how often the file metadata is checked when polling (default: 1 second).
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Forces polling even where inotify is available.
+    pub fn polling(mut self, polling: bool) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Sets a callback invoked after every reload attempt, from the watcher thread.
+    ///
+    /// The initial load performed by [`ReloadingRouterBuilder::build`] does not invoke it.
+    pub fn on_reload<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ReloadStatus) + Send + Sync + 'static,
+    {
+        self.on_reload = Some(Box::new(callback));
+        self
+    }
+
+    /// Loads the file and starts watching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial load fails, or if the watcher thread cannot be
+    /// spawned.
+    pub fn build(self) -> Result<ReloadingRouter, ConfigError> {
+        // Start watching before the initial load, so that no change can be missed.
+        let watcher = Watcher::new(&self.path, self.polling, self.poll_interval);
+        let router = RouteConfig::load(&self.path)?.build()?;
+        let shared = Arc::new(Shared {
+            path: self.path,
+            current: RwLock::new(Arc::new(router)),
+            on_reload: self.on_reload,
+            reloading: Mutex::new(()),
+            stop: AtomicBool::new(false),
+            wakeup: (Mutex::new(()), Condvar::new()),
+        });
+
+        let watched = Arc::clone(&shared);
+        let thread = thread::Builder::new()
+            .name("route-config-watcher".to_string())
+            .spawn(move || watcher.run(&watched))
+            .map_err(|e| {
+                let message = format!("failed to spawn route config watcher: {}", e);
+                ConfigError::new(ConfigErrorKind::Io, message, None)
+            })?;
+
+        Ok(ReloadingRouter {
+            shared,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// A [`Router`] kept in sync with a route-table file.
+///
+/// Readers call [`ReloadingRouter::current`] to get the active router. Swapping is atomic:
+/// a reader keeps using the router it obtained, while later calls see the new one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use radix_route_matcher::{ReloadStatus, ReloadingRouter};
+///
+/// let routes = ReloadingRouter::builder("routes.yaml")
+///     .on_reload(|status| {
+///         if let ReloadStatus::Failed(err) = status {
+///             eprintln!("keeping previous routes: {}", err);
+///         }
+///     })
+///     .build()
+///     .unwrap();
+///
+/// let router = routes.current();
+/// let iter = router.create_iter().unwrap();
+/// let route = router.match_route(&iter, "/api/users", None, Some("GET"));
+/// ```
+pub struct ReloadingRouter {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ReloadingRouter {
+    /// Returns a builder watching the route-table file at `path`.
+    ///
+    /// The format is guessed from the file extension, as in [`RouteConfig::load`].
+    pub fn builder<P: AsRef<Path>>(path: P) -> ReloadingRouterBuilder {
+        ReloadingRouterBuilder {
+            path: path.as_ref().to_path_buf(),
+            poll_interval: Duration::from_secs(1),
+            polling: false,
+            on_reload: None,
+        }
+    }
+
+    /// Loads the route-table file at `path` and starts watching it with default options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial load fails.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Self::builder(path).build()
+    }
+
+    /// Returns the active router.
+    pub fn current(&self) -> Arc<Router> {
+        Arc::clone(&self.shared.current.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Reloads the file immediately, regardless of changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, in which case the previous router stays active.
+    pub fn reload(&self) -> Result<(), ConfigError> {
+        self.shared.reload()
+    }
+}
+
+impl Drop for ReloadingRouter {
+    fn drop(&mut self) {
+        self.shared.stop.store(true, Ordering::Release);
+        {
+            let _guard = self.shared.wakeup.0.lock().unwrap_or_else(|e| e.into_inner());
+            self.shared.wakeup.1.notify_all();
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Time given to writers to finish a burst of changes before reloading.
+const SETTLE_DELAY: Duration = Duration::from_millis(50);
+
+/// Identifies a version of the file, as far as metadata can tell.
+fn fingerprint(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// The change detection strategy used by the watcher thread.
+enum Watcher {
+    #[cfg(target_os = "linux")]
+    Inotify(inotify::Watcher),
+    Poll {
+        interval: Duration,
+        last: Option<(SystemTime, u64)>,
+    },
+}
+
+impl Watcher {
+    fn new(path: &Path, polling: bool, interval: Duration) -> Self {
+        #[cfg(target_os = "linux")]
+        if !polling {
+            if let Some(watcher) = inotify::Watcher::new(path) {
+                return Watcher::Inotify(watcher);
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = polling;
+        Watcher::Poll {
+            interval,
+            last: fingerprint(path),
+        }
+    }
+
+    fn run(self, shared: &Shared) {
+        match self {
+            #[cfg(target_os = "linux")]
+            Watcher::Inotify(watcher) => watcher.run(shared),
+            Watcher::Poll { interval, mut last } => {
+                while !shared.wait(interval) {
+                    let current = fingerprint(&shared.path);
+                    if current.is_none() || current == last {
+                        continue;
+                    }
+                    if shared.wait(SETTLE_DELAY) {
+                        return;
+                    }
+                    last = fingerprint(&shared.path);
+                    let _ = shared.reload();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use super::{Shared, SETTLE_DELAY};
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+    use std::sync::atomic::Ordering;
+
+    /// How often the stop flag is checked while waiting for events.
+    const STOP_CHECK_MS: libc::c_int = 100;
+
+    /// Watches the directory holding the file, so that replacing the file by rename
+    /// (as most editors and deployment tools do) is detected too.
+    pub(super) struct Watcher {
+        fd: libc::c_int,
+        name: Vec<u8>,
+    }
+
+    impl Watcher {
+        pub(super) fn new(path: &Path) -> Option<Self> {
+            let name = path.file_name()?.as_bytes().to_vec();
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            let dir = CString::new(dir.as_os_str().as_bytes()).ok()?;
+            let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return None;
+            }
+            let mask = libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO | libc::IN_CREATE;
+            if unsafe { libc::inotify_add_watch(fd, dir.as_ptr(), mask) } < 0 {
+                unsafe { libc::close(fd) };
+                return None;
+            }
+            Some(Self { fd, name })
+        }
+
+        /// Waits for events, returning `true` if one of them concerns the watched file.
+        fn wait(&self, timeout_ms: libc::c_int) -> bool {
+            let mut pfd = libc::pollfd {
+                fd: self.fd,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut pfd, 1, timeout_ms) } <= 0 {
+                return false;
+            }
+            let mut matched = false;
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = unsafe { libc::read(self.fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+                if n <= 0 {
+                    return matched;
+                }
+                let mut pos = 0;
+                let header = std::mem::size_of::<libc::inotify_event>();
+                while pos + header <= n as usize {
+                    let event =
+                        unsafe { std::ptr::read_unaligned(buf.as_ptr().add(pos) as *const libc::inotify_event) };
+                    let name = &buf[pos + header..pos + header + event.len as usize];
+                    let name = name.split(|&b| b == 0).next().unwrap_or_default();
+                    if name == self.name.as_slice() {
+                        matched = true;
+                    }
+                    pos += header + event.len as usize;
+                }
+            }
+        }
+
+        pub(super) fn run(self, shared: &Shared) {
+            while !shared.stop.load(Ordering::Acquire) {
+                if !self.wait(STOP_CHECK_MS) {
+                    continue;
+                }
+                // Let the writer finish, and coalesce the events it generates.
+                if shared.wait(SETTLE_DELAY) {
+                    return;
+                }
+                while self.wait(0) {}
+                let _ = shared.reload();
+            }
+        }
+    }
+
+    impl Drop for Watcher {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+}
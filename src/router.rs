@@ -0,0 +1,325 @@
+//! HTTP-style router built on the Radix Tree
+//!
+//! This model combines a `RadixTree` keyed by path with per-route host, method and
+//! priority constraints, so that a request can be resolved to a single route id.
+
+use crate::radix_tree::{RadixIterator, RadixTree};
+use std::collections::HashSet;
+use std::fmt;
+
+/// HTTP methods accepted in route definitions.
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "CONNECT", "TRACE",
+];
+
+/// A route definition.
+///
+/// # Path Patterns
+///
+/// A path must start with `/`. A trailing `*` turns it into a prefix pattern: `/api/*`
+/// matches every path starting with `/api/`, while `/api/users` only matches itself.
+/// `*` is not allowed anywhere else.
+///
+/// # Hosts and Methods
+///
+/// `hosts` lists the host names the route applies to, compared case-insensitively. A host
+/// starting with `*.` matches any subdomain, e.g. `*.example.com` matches `a.example.com`.
+/// Host names are ASCII: internationalized names are given in their punycode form
+/// (`xn--bcher-kva.example` for `bücher.example`), as they appear in requests.
+/// `methods` lists HTTP methods. Empty lists match any host or method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct Route {
+    /// Route identifier returned on match (must be > 0 and unique).
+    pub id: i32,
+    /// Path patterns, see above.
+    pub paths: Vec<String>,
+    /// Host names, see above.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hosts: Vec<String>,
+    /// HTTP methods, see above.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub methods: Vec<String>,
+    /// When several routes match the same path, the highest priority wins.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: i32,
+}
+
+/// Error returned when a route cannot be added to a [`Router`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// The route id is not strictly positive.
+    InvalidId(i32),
+    /// A route with the same id was already added.
+    DuplicateId(i32),
+    /// The route has no path.
+    NoPaths,
+    /// A path pattern is malformed.
+    InvalidPath(String),
+    /// A host pattern is malformed.
+    InvalidHost(String),
+    /// A method is not a known HTTP method.
+    InvalidMethod(String),
+    /// Another route with the same path, hosts, methods and priority was already added.
+    DuplicatePath(String),
+    /// The underlying tree rejected the insertion.
+    Tree(i32),
+}
+
+impl fmt::Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteError::InvalidId(id) => write!(f, "invalid route id {} (must be > 0)", id),
+            RouteError::DuplicateId(id) => write!(f, "duplicate route id {}", id),
+            RouteError::NoPaths => write!(f, "route has no paths"),
+            RouteError::InvalidPath(path) => write!(f, "invalid path pattern {:?}", path),
+            RouteError::InvalidHost(host) => write!(f, "invalid host pattern {:?}", host),
+            RouteError::InvalidMethod(method) => write!(f, "invalid HTTP method {:?}", method),
+            RouteError::DuplicatePath(path) => write!(f, "duplicate route for path {:?}", path),
+            RouteError::Tree(rc) => write!(f, "radix tree insertion failed with error {}", rc),
+        }
+    }
+}
+
+impl std::error::Error for RouteError {}
+
+/// A route registered under one tree key.
+struct Entry {
+    route: usize,
+    prefix: bool,
+}
+
+/// All the routes sharing the same tree key.
+struct Slot {
+    key_len: usize,
+    entries: Vec<Entry>,
+}
+
+/// A path with the hosts, methods and priority of a route, which no other route may repeat.
+type Claim = (String, Vec<String>, Vec<String>, i32);
+
+/// A router resolving (path, host, method) requests to route ids.
+///
+/// The longest matching path wins. Among routes registered for that path, those whose
+/// hosts and methods accept the request are considered, and the highest priority wins
+/// (the first added route on ties).
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::{Route, Router};
+///
+/// let mut router = Router::new().unwrap();
+/// router
+///     .add(Route {
+///         id: 1,
+///         paths: vec!["/api/*".into()],
+///         hosts: vec![],
+///         methods: vec![],
+///         priority: 0,
+///     })
+///     .unwrap();
+/// router
+///     .add(Route {
+///         id: 2,
+///         paths: vec!["/api/users".into()],
+///         hosts: vec!["*.example.com".into()],
+///         methods: vec!["GET".into()],
+///         priority: 0,
+///     })
+///     .unwrap();
+///
+/// let iter = router.create_iter().unwrap();
+/// assert_eq!(router.match_route(&iter, "/api/users", Some("www.example.com"), Some("GET")), Some(2));
+/// assert_eq!(router.match_route(&iter, "/api/users", Some("www.example.com"), Some("POST")), Some(1));
+/// assert_eq!(router.match_route(&iter, "/static", None, None), None);
+/// ```
+pub struct Router {
+    tree: RadixTree,
+    slots: Vec<Slot>,
+    routes: Vec<Route>,
+    ids: HashSet<i32>,
+    claims: HashSet<Claim>,
+}
+
+/// Splits a path pattern into its tree key and whether it is a prefix pattern.
+fn parse_path(path: &str) -> Result<(&str, bool), RouteError> {
+    let invalid = || RouteError::InvalidPath(path.to_string());
+    if !path.starts_with('/') || path.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(invalid());
+    }
+    let (key, prefix) = match path.strip_suffix('*') {
+        Some(key) => (key, true),
+        None => (path, false),
+    };
+    if key.contains('*') {
+        return Err(invalid());
+    }
+    Ok((key, prefix))
+}
+
+fn valid_host(host: &str) -> bool {
+    let name = host.strip_prefix("*.").unwrap_or(host);
+    !name.is_empty()
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        })
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => {
+            let (host, suffix) = (host.as_bytes(), suffix.as_bytes());
+            host.len() > suffix.len() + 1
+                && host[host.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+                && host[host.len() - suffix.len() - 1] == b'.'
+        }
+        None => pattern.eq_ignore_ascii_case(host),
+    }
+}
+
+impl Router {
+    /// Creates a new empty router.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    pub fn new() -> Result<Self, &'static str> {
+        Ok(Self {
+            tree: RadixTree::new()?,
+            slots: Vec::new(),
+            routes: Vec::new(),
+            ids: HashSet::new(),
+            claims: HashSet::new(),
+        })
+    }
+
+    /// Creates a new iterator for matching requests against this router.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if iterator allocation fails.
+    pub fn create_iter(&self) -> Result<RadixIterator, &'static str> {
+        self.tree.create_iter()
+    }
+
+    /// Returns the routes added so far, in insertion order.
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Validates a route and adds it to the router.
+    ///
+    /// Host names are normalized to lowercase and methods to uppercase.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the route is invalid or duplicates an existing one. The router
+    /// is left unchanged in that case.
+    pub fn add(&mut self, mut route: Route) -> Result<(), RouteError> {
+        if route.id <= 0 {
+            return Err(RouteError::InvalidId(route.id));
+        }
+        if self.ids.contains(&route.id) {
+            return Err(RouteError::DuplicateId(route.id));
+        }
+        if route.paths.is_empty() {
+            return Err(RouteError::NoPaths);
+        }
+        for host in &mut route.hosts {
+            if !valid_host(host) {
+                return Err(RouteError::InvalidHost(host.clone()));
+            }
+            host.make_ascii_lowercase();
+        }
+        for method in &mut route.methods {
+            let upper = method.to_ascii_uppercase();
+            if !METHODS.contains(&upper.as_str()) {
+                return Err(RouteError::InvalidMethod(method.clone()));
+            }
+            *method = upper;
+        }
+        route.hosts.sort();
+        route.hosts.dedup();
+        route.methods.sort();
+        route.methods.dedup();
+
+        let mut seen = HashSet::with_capacity(route.paths.len());
+        let mut claims = Vec::with_capacity(route.paths.len());
+        for path in &route.paths {
+            parse_path(path)?;
+            let claim = (path.clone(), route.hosts.clone(), route.methods.clone(), route.priority);
+            if !seen.insert(path) || self.claims.contains(&claim) {
+                return Err(RouteError::DuplicatePath(path.clone()));
+            }
+            claims.push(claim);
+        }
+
+        // Everything is validated: the insertions below can only fail on allocation.
+        let index = self.routes.len();
+        for path in &route.paths {
+            let (key, prefix) = parse_path(path)?;
+            let slot = match self.tree.find_exact(key) {
+                Some(slot) => slot as usize - 1,
+                None => {
+                    self.tree
+                        .insert(key, self.slots.len() as i32 + 1)
+                        .map_err(RouteError::Tree)?;
+                    self.slots.push(Slot {
+                        key_len: key.len(),
+                        entries: Vec::new(),
+                    });
+                    self.slots.len() - 1
+                }
+            };
+            self.slots[slot].entries.push(Entry { route: index, prefix });
+        }
+        self.ids.insert(route.id);
+        self.claims.extend(claims);
+        self.routes.push(route);
+        Ok(())
+    }
+
+    /// Resolves a request to a route id.
+    ///
+    /// `host` and `method` are optional: when absent, only routes that do not restrict
+    /// hosts (respectively methods) can match.
+    ///
+    /// # Returns
+    ///
+    /// Returns the id of the matching route, or `None` if no route matches.
+    pub fn match_route(
+        &self,
+        iter: &RadixIterator,
+        path: &str,
+        host: Option<&str>,
+        method: Option<&str>,
+    ) -> Option<i32> {
+        for found in self.tree.find_all_prefixes(iter, path) {
+            let slot = &self.slots[found.value as usize - 1];
+            let best = slot
+                .entries
+                .iter()
+                .filter(|entry| entry.prefix || slot.key_len == path.len())
+                .map(|entry| &self.routes[entry.route])
+                .filter(|route| {
+                    route.hosts.is_empty() || host.is_some_and(|h| route.hosts.iter().any(|p| host_matches(p, h)))
+                })
+                .filter(|route| {
+                    route.methods.is_empty()
+                        || method.is_some_and(|m| route.methods.iter().any(|p| p.eq_ignore_ascii_case(m)))
+                })
+                // max_by_key returns the last maximum, so reverse to prefer the first added route.
+                .rev()
+                .max_by_key(|route| route.priority);
+            if let Some(route) = best {
+                return Some(route.id);
+            }
+        }
+        None
+    }
+}
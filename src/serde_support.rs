@@ -0,0 +1,133 @@
+//! Serde support for RadixTree
+//!
+//! This model implements `Serialize` and `Deserialize` for `RadixTree`, behind the
+//! optional `serde` feature.
+//!
+//! A tree is serialized as an ordered sequence of `[key, index]` pairs, whatever its keys.
+//! In human-readable formats (JSON, YAML, ...) each key is a string if it is valid UTF-8
+//! and a byte array otherwise; other formats always use byte keys.
+//!
+//! Deserialization also accepts a map from key to index, and builds a case-sensitive tree.
+//! Neither form has room for the case folding of a tree, so case-insensitive trees cannot
+//! be serialized:
+//! their entries are to be serialized instead, and inserted into a tree created with
+//! [`RadixTree::with_case_folding`] when loaded.
+
+use crate::case::CaseFolding;
+use crate::radix_tree::RadixTree;
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use std::fmt;
+
+/// A key borrowed for serialization: a string when possible, raw bytes otherwise.
+struct KeyRef<'a>(&'a [u8]);
+
+impl Serialize for KeyRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match std::str::from_utf8(self.0) {
+            Ok(key) if serializer.is_human_readable() => serializer.serialize_str(key),
+            _ => serializer.serialize_bytes(self.0),
+        }
+    }
+}
+
+impl Serialize for RadixTree {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        if self.case_folding() != CaseFolding::Sensitive {
+            return Err(S::Error::custom(
+                "a case-insensitive tree cannot be serialized, since its case folding would be lost",
+            ));
+        }
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for (key, idx) in self.iter().map_err(S::Error::custom)? {
+            seq.serialize_element(&(KeyRef(&key), idx))?;
+        }
+        seq.end()
+    }
+}
+
+/// An owned key accepting strings, byte strings, and sequences of bytes.
+struct KeyBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for KeyBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = KeyBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string or byte array key")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<KeyBuf, E> {
+                Ok(KeyBuf(v.as_bytes().to_vec()))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<KeyBuf, E> {
+                Ok(KeyBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<KeyBuf, E> {
+                Ok(KeyBuf(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KeyBuf, A::Error> {
+                let mut key = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    key.push(byte);
+                }
+                Ok(KeyBuf(key))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(KeyVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(KeyVisitor)
+        }
+    }
+}
+
+fn insert_entry<E: de::Error>(tree: &mut RadixTree, key: &[u8], idx: i32) -> Result<(), E> {
+    tree.insert(key, idx)
+        .map_err(|rc| E::custom(format_args!("failed to insert key (error {})", rc)))
+}
+
+struct TreeVisitor;
+
+impl<'de> Visitor<'de> for TreeVisitor {
+    type Value = RadixTree;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a map of keys to indices, or a sequence of [key, index] pairs")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RadixTree, A::Error> {
+        let mut tree = RadixTree::new().map_err(de::Error::custom)?;
+        while let Some((KeyBuf(key), idx)) = map.next_entry::<KeyBuf, i32>()? {
+            insert_entry(&mut tree, &key, idx)?;
+        }
+        Ok(tree)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<RadixTree, A::Error> {
+        let mut tree = RadixTree::new().map_err(de::Error::custom)?;
+        while let Some((KeyBuf(key), idx)) = seq.next_element::<(KeyBuf, i32)>()? {
+            insert_entry(&mut tree, &key, idx)?;
+        }
+        Ok(tree)
+    }
+}
+
+impl<'de> Deserialize<'de> for RadixTree {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TreeVisitor)
+        } else {
+            deserializer.deserialize_seq(TreeVisitor)
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Value storage for collections built on the Radix Tree
+//!
+//! A tree only stores positive `i32` values, so collections holding arbitrary values keep
+//! them in a [`Slab`] and store the slab index (plus one) in the tree.
+
+/// A list of values addressed by stable indices. Removed values leave a hole in the list,
+/// which the next insertion reuses.
+pub(crate) struct Slab<T> {
+    entries: Vec<Option<T>>,
+    free: Vec<usize>,
+}
+
+impl<T> Slab<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+
+    /// Returns the tree value of the index the next insertion will use.
+    pub(crate) fn next_value(&self) -> i32 {
+        let index = self.free.last().copied().unwrap_or(self.entries.len());
+        index as i32 + 1
+    }
+
+    /// Stores a value, returning its tree value.
+    pub(crate) fn insert(&mut self, value: T) -> i32 {
+        match self.free.pop() {
+            Some(index) => {
+                self.entries[index] = Some(value);
+                index as i32 + 1
+            }
+            None => {
+                self.entries.push(Some(value));
+                self.entries.len() as i32
+            }
+        }
+    }
+
+    /// Returns the value stored under a tree value.
+    pub(crate) fn get(&self, value: i32) -> Option<&T> {
+        self.entries.get(value as usize - 1)?.as_ref()
+    }
+
+    pub(crate) fn get_mut(&mut self, value: i32) -> Option<&mut T> {
+        self.entries.get_mut(value as usize - 1)?.as_mut()
+    }
+
+    pub(crate) fn remove(&mut self, value: i32) -> Option<T> {
+        let removed = self.entries.get_mut(value as usize - 1)?.take();
+        if removed.is_some() {
+            self.free.push(value as usize - 1);
+        }
+        removed
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter().flatten()
+    }
+
+    /// Removes all the values.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.free.clear();
+        self.entries.drain(..).flatten()
+    }
+}
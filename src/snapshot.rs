@@ -0,0 +1,330 @@
+//! Binary snapshots of a Radix Tree
+//!
+//! This model serializes a tree node by node, preserving the compressed layout of rax,
+//! so that loading a snapshot only allocates and copies nodes instead of replaying
+//! `raxInsert` for every key.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! ```text
+//! magic     4 bytes   "RRMS"
+//! version   u16       SNAPSHOT_VERSION
+//! flags     u16       case folding: 0 sensitive, 1 ASCII, 2 Unicode; other bits reserved, 0
+//! numele    u64       number of keys
+//! numnodes  u64       number of nodes
+//! body_len  u64       length of the body in bytes
+//! body      body_len  nodes in depth-first pre-order, then spellings
+//! checksum  u32       CRC-32 (IEEE) of the body
+//! ```
+//!
+//! Each node is encoded as a `u32` header (`iskey | isnull << 1 | iscompr << 2 | size << 3`),
+//! followed by its `size` characters and, for keys with a value, an `i64` value.
+//!
+//! The nodes of a case-insensitive tree hold the folded keys. They are followed by the
+//! keys inserted in another spelling, each as a `u32` length and the bytes of the key as
+//! inserted, in byte order.
+
+use crate::case::CaseFolding;
+use crate::ffi::*;
+use crate::node::Node;
+use crate::radix_tree::RadixTree;
+use libc::c_void;
+use std::io::{self, Read, Write};
+use std::ptr;
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"RRMS";
+const SNAPSHOT_VERSION: u16 = 1;
+const HEADER_LEN: usize = 4 + 2 + 2 + 8 + 8 + 8;
+
+const NODE_ISKEY: u32 = 1 << 0;
+const NODE_ISNULL: u32 = 1 << 1;
+const NODE_ISCOMPR: u32 = 1 << 2;
+const NODE_SIZE_SHIFT: u32 = 3;
+
+/// Builds the CRC-32 (IEEE 802.3) lookup table at compile time.
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC32_TABLE: [u32; 256] = crc32_table();
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Appends the encoding of every node under `head` to `out`, in pre-order.
+fn encode_nodes(head: Node<'_>, out: &mut Vec<u8>) {
+    let mut stack = vec![head];
+    while let Some(node) = stack.pop() {
+        let chars = node.chars();
+        let mut header = (chars.len() as u32) << NODE_SIZE_SHIFT;
+        if node.is_key() {
+            header |= NODE_ISKEY;
+        }
+        if node.is_null() {
+            header |= NODE_ISNULL;
+        }
+        if node.is_compressed() {
+            header |= NODE_ISCOMPR;
+        }
+        out.extend_from_slice(&header.to_le_bytes());
+        out.extend_from_slice(chars);
+        if let Some(data) = node.data() {
+            out.extend_from_slice(&(data as isize as i64).to_le_bytes());
+        }
+        // Push in reverse so that the first child is encoded first.
+        for i in (0..node.child_count()).rev() {
+            stack.push(node.child(i));
+        }
+    }
+}
+
+/// Cursor over the node section of a snapshot.
+struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.buf.len() - self.pos < len {
+            return Err(invalid("truncated snapshot node"));
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> io::Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Decodes and allocates the next node. Its child pointers are left NULL.
+    fn node(&mut self) -> io::Result<(*mut RaxNode, usize)> {
+        let header = self.u32()?;
+        let size = (header >> NODE_SIZE_SHIFT) as usize;
+        let iskey = header & NODE_ISKEY != 0;
+        let isnull = header & NODE_ISNULL != 0;
+        let iscompr = header & NODE_ISCOMPR != 0;
+        if iscompr && size == 0 {
+            return Err(invalid("empty compressed node in snapshot"));
+        }
+        let chars = self.take(size)?;
+        if !iscompr && chars.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(invalid("unsorted node children in snapshot"));
+        }
+        let data = if iskey && !isnull {
+            let value = self.i64()?;
+            if value == 0 {
+                return Err(invalid("null value stored in non-null snapshot node"));
+            }
+            value as isize as *mut c_void
+        } else {
+            ptr::null_mut()
+        };
+
+        let node = unsafe { raxNodeCreate(iscompr as i32, size, chars.as_ptr(), iskey as i32, data) };
+        if node.is_null() {
+            return Err(io::Error::new(io::ErrorKind::OutOfMemory, "failed to allocate snapshot node"));
+        }
+        let children = if iscompr { 1 } else { size };
+        Ok((node, children))
+    }
+
+    /// Rebuilds the whole tree, returning its head node and the number of keys and nodes.
+    ///
+    /// On error every node allocated so far is released.
+    fn tree(&mut self) -> io::Result<(*mut RaxNode, u64, u64)> {
+        let mut numele = 0u64;
+        let mut numnodes = 0u64;
+        let (head, children) = self.node()?;
+        // Stack of (node, index of the next child to fill, number of children).
+        let mut stack = vec![(head, 0usize, children)];
+
+        while let Some(top) = stack.last_mut() {
+            let (parent, next, children) = *top;
+            if next == children {
+                numnodes += 1;
+                if unsafe { raxNodeIsKey(parent) } != 0 {
+                    numele += 1;
+                }
+                stack.pop();
+                continue;
+            }
+            top.1 += 1;
+            match self.node() {
+                Ok((child, grandchildren)) => {
+                    unsafe { raxNodeSetChild(parent, next, child) };
+                    stack.push((child, 0, grandchildren));
+                }
+                Err(err) => {
+                    unsafe { raxNodeFree(head) };
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok((head, numele, numnodes))
+    }
+
+    /// Decodes the spellings following the nodes, up to the end of the body.
+    fn spellings(&mut self) -> io::Result<Vec<&'a [u8]>> {
+        let mut spellings = Vec::new();
+        while self.pos != self.buf.len() {
+            let len = self.u32()? as usize;
+            spellings.push(self.take(len)?);
+        }
+        Ok(spellings)
+    }
+}
+
+impl RadixTree {
+    /// Writes a binary snapshot of the tree.
+    ///
+    /// The snapshot is versioned and checksummed, and preserves the node layout of the
+    /// tree so that [`RadixTree::read_snapshot`] can rebuild it without re-inserting keys.
+    /// The case folding of the tree, and the spelling its keys were inserted with, are
+    /// kept too.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error reported by the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert("/api", 1).unwrap();
+    /// tree.insert("/api/users", 2).unwrap();
+    ///
+    /// let mut buf = Vec::new();
+    /// tree.write_snapshot(&mut buf).unwrap();
+    ///
+    /// let loaded = RadixTree::read_snapshot(&buf[..]).unwrap();
+    /// assert_eq!(loaded.find_exact("/api/users"), Some(2));
+    /// ```
+    pub fn write_snapshot<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let rax = self.tree as *const Rax;
+        let mut body = Vec::new();
+        let (numele, numnodes) = unsafe {
+            encode_nodes(Node::head(rax), &mut body);
+            ((*rax).numele, (*rax).numnodes)
+        };
+        let mut spellings: Vec<_> = self.spelled_keys().collect();
+        spellings.sort_unstable();
+        for spelling in spellings {
+            let len = u32::try_from(spelling.len()).map_err(|_| invalid("snapshot key exceeds 4 GiB"))?;
+            body.extend_from_slice(&len.to_le_bytes());
+            body.extend_from_slice(spelling);
+        }
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(SNAPSHOT_MAGIC);
+        header.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        header.extend_from_slice(&self.case_folding().flags().to_le_bytes());
+        header.extend_from_slice(&numele.to_le_bytes());
+        header.extend_from_slice(&numnodes.to_le_bytes());
+        header.extend_from_slice(&(body.len() as u64).to_le_bytes());
+
+        writer.write_all(&header)?;
+        writer.write_all(&body)?;
+        writer.write_all(&crc32(&body).to_le_bytes())
+    }
+
+    /// Loads a tree from a snapshot written by [`RadixTree::write_snapshot`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading fails, or with [`io::ErrorKind::InvalidData`] if the
+    /// snapshot is truncated, corrupted, or was written by an unsupported version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// assert!(RadixTree::read_snapshot(&b"not a snapshot"[..]).is_err());
+    /// ```
+    pub fn read_snapshot<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        if &header[0..4] != SNAPSHOT_MAGIC {
+            return Err(invalid("not a radix tree snapshot"));
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != SNAPSHOT_VERSION {
+            return Err(invalid("unsupported snapshot version"));
+        }
+        let folding = CaseFolding::from_flags(u16::from_le_bytes([header[6], header[7]]))
+            .ok_or_else(|| invalid("unsupported snapshot flags"))?;
+        let numele = u64::from_le_bytes(header[8..16].try_into().unwrap());
+        let numnodes = u64::from_le_bytes(header[16..24].try_into().unwrap());
+        let body_len = u64::from_le_bytes(header[24..32].try_into().unwrap());
+
+        let mut body = Vec::new();
+        reader.by_ref().take(body_len).read_to_end(&mut body)?;
+        if body.len() as u64 != body_len {
+            return Err(invalid("truncated snapshot"));
+        }
+        let mut checksum = [0u8; 4];
+        reader.read_exact(&mut checksum)?;
+        if u32::from_le_bytes(checksum) != crc32(&body) {
+            return Err(invalid("snapshot checksum mismatch"));
+        }
+
+        // The tree is created first, so that the decoded nodes are charged to it.
+        let mut tree = RadixTree::with_case_folding(folding)
+            .map_err(|_| io::Error::new(io::ErrorKind::OutOfMemory, "failed to allocate radix tree"))?;
+        let mut decoder = Decoder { buf: &body, pos: 0 };
+        tree.charged(|rax| {
+            let (head, found_ele, found_nodes) = decoder.tree()?;
+            if found_ele != numele || found_nodes != numnodes {
+                unsafe { raxNodeFree(head) };
+                return Err(invalid("snapshot node count mismatch"));
+            }
+            unsafe {
+                let rax = rax as *mut Rax;
+                raxNodeFree((*rax).head);
+                (*rax).head = head;
+                (*rax).numele = numele;
+                (*rax).numnodes = numnodes;
+            }
+            Ok(())
+        })?;
+
+        for spelling in decoder.spellings()? {
+            if !tree.restore_spelling(spelling) {
+                return Err(invalid("snapshot spelling of a missing key"));
+            }
+        }
+        Ok(tree)
+    }
+}
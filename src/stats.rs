@@ -0,0 +1,137 @@
+//! Memory usage and shape statistics of a Radix Tree
+//!
+//! This model walks the nodes of a tree to report how much memory each kind of node
+//! takes, and how the tree is shaped: how deep keys are, and how much nodes branch.
+
+use crate::alloc::block_size;
+use crate::ffi::Rax;
+use crate::node::Node;
+use crate::radix_tree::RadixTree;
+
+/// Memory allocated for a tree, by kind of node.
+///
+/// Sizes are the bytes requested from the allocator, without its bookkeeping overhead.
+/// Compressed nodes hold a string leading to a single child, branching nodes one byte per
+/// child, and leaf nodes terminate a key without any child.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The tree header.
+    pub header: usize,
+    /// Compressed nodes.
+    pub compressed: usize,
+    /// Branching nodes, with one or more children.
+    pub branching: usize,
+    /// Leaf nodes, without children.
+    pub leaf: usize,
+}
+
+impl MemoryUsage {
+    /// Returns the total number of bytes, equal to [`RadixTree::allocated_bytes`].
+    pub fn total(&self) -> usize {
+        self.header + self.compressed + self.branching + self.leaf
+    }
+}
+
+/// Shape and memory statistics of a tree, computed by [`RadixTree::stats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TreeStats {
+    /// Number of keys.
+    pub keys: usize,
+    /// Number of nodes, including the head node.
+    pub nodes: usize,
+    /// Number of compressed nodes.
+    pub compressed_nodes: usize,
+    /// Number of branching nodes.
+    pub branching_nodes: usize,
+    /// Number of leaf nodes.
+    pub leaf_nodes: usize,
+    /// Number of keys by depth: `depth_histogram[d]` keys are `d` nodes below the head,
+    /// which is the number of nodes a lookup of these keys visits after the head.
+    pub depth_histogram: Vec<usize>,
+    /// Average number of children of the branching nodes, or 0 if there is none.
+    pub average_fan_out: f64,
+    /// Memory allocated for the tree.
+    pub memory: MemoryUsage,
+}
+
+impl TreeStats {
+    /// Returns the depth of the deepest key, or `None` for an empty tree.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.depth_histogram.len().checked_sub(1)
+    }
+}
+
+impl RadixTree {
+    /// Returns the memory allocated for the tree, by kind of node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api/users", 1), ("/api/posts", 2)]).unwrap();
+    ///
+    /// let usage = tree.memory_usage();
+    /// assert!(usage.branching > 0);
+    /// assert_eq!(usage.total(), tree.allocated_bytes());
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.stats().memory
+    }
+
+    /// Walks the tree and returns its shape and memory statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use radix_route_matcher::RadixTree;
+    ///
+    /// let mut tree = RadixTree::new().unwrap();
+    /// tree.insert_many([("/api", 1), ("/api/users", 2), ("/api/posts", 3)]).unwrap();
+    ///
+    /// let stats = tree.stats();
+    /// assert_eq!(stats.keys, 3);
+    /// assert_eq!(stats.nodes, tree.node_count());
+    /// assert_eq!(stats.depth_histogram, vec![0, 1, 0, 0, 2]);
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        let rax = self.tree as *const Rax;
+        let mut stats = TreeStats {
+            memory: MemoryUsage {
+                header: unsafe { block_size(rax.cast()) },
+                ..MemoryUsage::default()
+            },
+            ..TreeStats::default()
+        };
+        let mut children = 0;
+        let mut stack = vec![(unsafe { Node::head(rax) }, 0usize)];
+        while let Some((node, depth)) = stack.pop() {
+            stats.nodes += 1;
+            let bytes = node.allocated_bytes();
+            if node.is_compressed() {
+                stats.compressed_nodes += 1;
+                stats.memory.compressed += bytes;
+            } else if node.child_count() > 0 {
+                stats.branching_nodes += 1;
+                stats.memory.branching += bytes;
+                children += node.child_count();
+            } else {
+                stats.leaf_nodes += 1;
+                stats.memory.leaf += bytes;
+            }
+            if node.is_key() {
+                stats.keys += 1;
+                if stats.depth_histogram.len() <= depth {
+                    stats.depth_histogram.resize(depth + 1, 0);
+                }
+                stats.depth_histogram[depth] += 1;
+            }
+            stack.extend((0..node.child_count()).map(|i| (node.child(i), depth + 1)));
+        }
+        if stats.branching_nodes > 0 {
+            stats.average_fan_out = children as f64 / stats.branching_nodes as f64;
+        }
+        stats
+    }
+}
@@ -1,4 +1,8 @@
-use super::RadixTree;
+use super::{
+    CaseFolding, DomainError, DomainSet, FlatTree, GlobError, GlobRouter, IpPrefix, IpPrefixError, IpPrefixTable,
+    ListFormat, RadixTree, Route, RouteError, Router, TopicError, TopicMatcher,
+};
+use std::borrow::Cow;
 
 #[test]
 fn insert_and_find_exact() {
@@ -19,10 +23,26 @@ fn longest_prefix_and_all_prefixes() {
     tree.insert("/api/users", 3).unwrap();
 
     let iter = tree.create_iter().expect("create iter");
-    assert_eq!(tree.longest_prefix(&iter, "/api/users/123"), Some(3));
+    let found = tree.longest_prefix(&iter, "/api/users/123").unwrap();
+    assert_eq!((found.value, found.matched_len), (3, 10));
+    assert_eq!(found.key, &b"/api/users"[..]);
+    // The key borrows the path rather than being copied.
+    assert!(matches!(found.key, Cow::Borrowed(_)));
+    assert_eq!(found.matched(), b"/api/users");
+    assert_eq!(found.remainder(), b"/123");
 
     let prefixes = tree.find_all_prefixes(&iter, "/api/users/123");
-    assert_eq!(prefixes, vec![3, 2, 1]);
+    let values: Vec<i32> = prefixes.iter().map(|m| m.value).collect();
+    assert_eq!(values, vec![3, 2, 1]);
+    let remainders: Vec<&[u8]> = prefixes.iter().map(|m| m.remainder()).collect();
+    assert_eq!(remainders, vec![&b"/123"[..], b"/users/123", b"api/users/123"]);
+
+    // A key matching the whole path leaves an empty remainder.
+    assert!(tree.longest_prefix(&iter, "/api").unwrap().remainder().is_empty());
+    assert!(tree.search(&iter, "/api/x"));
+    assert_eq!(tree.next_prefix(&iter, "/api/x").unwrap().remainder(), b"/x");
+    assert_eq!(tree.next_prefix(&iter, "/api/x").unwrap().key, &b"/"[..]);
+    assert_eq!(tree.next_prefix(&iter, "/api/x"), None);
 }
 
 #[test]
@@ -36,3 +56,1573 @@ fn remove_routes() {
     assert_eq!(tree.find_exact("/foo/bar"), Some(11));
 }
 
+#[test]
+fn snapshot_roundtrip() {
+    let mut tree = RadixTree::new().expect("create tree");
+    let routes = [
+        "/",
+        "/api",
+        "/api/users",
+        "/api/posts",
+        "/apix",
+        "/static/中文",
+        "/foo/bar/baz",
+    ];
+    for (i, route) in routes.iter().enumerate() {
+        tree.insert(route, i as i32 + 1).unwrap();
+    }
+    tree.remove("/apix").unwrap();
+
+    let mut buf = Vec::new();
+    tree.write_snapshot(&mut buf).expect("write snapshot");
+    let loaded = RadixTree::read_snapshot(&buf[..]).expect("read snapshot");
+
+    for (i, route) in routes.iter().enumerate() {
+        assert_eq!(
+            loaded.find_exact(route),
+            tree.find_exact(route),
+            "route {} ({})",
+            route,
+            i
+        );
+    }
+    let iter = loaded.create_iter().unwrap();
+    let values: Vec<i32> = loaded
+        .find_all_prefixes(&iter, "/api/users/1")
+        .iter()
+        .map(|m| m.value)
+        .collect();
+    assert_eq!(values, vec![3, 2, 1]);
+
+    // The loaded tree must remain fully writable.
+    let mut loaded = loaded;
+    loaded.insert("/api/v2", 42).unwrap();
+    loaded.remove("/api").unwrap();
+    assert_eq!(loaded.find_exact("/api/v2"), Some(42));
+    assert_eq!(loaded.find_exact("/api"), None);
+    assert_eq!(loaded.find_exact("/api/users"), Some(3));
+}
+
+#[test]
+fn snapshot_rejects_corruption() {
+    let mut tree = RadixTree::new().expect("create tree");
+    tree.insert("/api", 1).unwrap();
+    tree.insert("/api/users", 2).unwrap();
+    let mut buf = Vec::new();
+    tree.write_snapshot(&mut buf).unwrap();
+
+    let mut corrupted = buf.clone();
+    let body_byte = 32 + 5;
+    corrupted[body_byte] ^= 0xff;
+    assert!(RadixTree::read_snapshot(&corrupted[..]).is_err());
+
+    assert!(RadixTree::read_snapshot(&buf[..buf.len() - 1]).is_err());
+
+    let mut bad_version = buf.clone();
+    bad_version[4] = 0xff;
+    assert!(RadixTree::read_snapshot(&bad_version[..]).is_err());
+
+    let empty = RadixTree::new().unwrap();
+    let mut buf = Vec::new();
+    empty.write_snapshot(&mut buf).unwrap();
+    let loaded = RadixTree::read_snapshot(&buf[..]).unwrap();
+    assert_eq!(loaded.find_exact(""), None);
+}
+
+#[test]
+fn flat_tree_matches_radix_tree() {
+    let mut tree = RadixTree::new().expect("create tree");
+    let routes = [
+        "/",
+        "/api",
+        "/api/users",
+        "/api/posts",
+        "/static/中文",
+        "/foo/bar/baz",
+        "/foo/baz",
+    ];
+    for (i, route) in routes.iter().enumerate() {
+        tree.insert(route, i as i32 + 1).unwrap();
+    }
+    let mut buf = Vec::new();
+    tree.write_flat(&mut buf).expect("write flat");
+    let flat = FlatTree::new(&buf[..]).expect("open flat");
+    assert_eq!(flat.len(), routes.len() as u64);
+
+    let iter = tree.create_iter().unwrap();
+    let paths = [
+        "/",
+        "/api",
+        "/api/",
+        "/api/users/1",
+        "/ap",
+        "/static/中文/x",
+        "/foo/bar",
+        "/foo/bar/baz/q",
+        "x",
+        "",
+    ];
+    for path in paths {
+        assert_eq!(flat.find_exact(path), tree.find_exact(path), "find_exact({:?})", path);
+        assert_eq!(
+            flat.longest_prefix(path),
+            tree.longest_prefix(&iter, path),
+            "longest_prefix({:?})",
+            path
+        );
+        assert_eq!(
+            flat.find_all_prefixes(path),
+            tree.find_all_prefixes(&iter, path),
+            "find_all_prefixes({:?})",
+            path
+        );
+    }
+
+    // Corrupted buffers are rejected or simply stop matching, they never panic.
+    assert!(FlatTree::new(&buf[..buf.len() - 1]).is_err());
+    let mut corrupted = buf.clone();
+    for byte in corrupted.iter_mut().skip(24) {
+        *byte = 0xff;
+    }
+    let flat = FlatTree::new(&corrupted[..]).unwrap();
+    assert!(flat.longest_prefix("/api/users").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn flat_tree_mmap() {
+    let mut tree = RadixTree::new().expect("create tree");
+    tree.insert("/api", 1).unwrap();
+    tree.insert("/api/users", 2).unwrap();
+
+    let path = std::env::temp_dir().join(format!("radix_route_matcher_flat_{}.bin", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    tree.write_flat(&mut file).unwrap();
+    drop(file);
+
+    // The file is private to this test and not modified while mapped.
+    let flat = unsafe { FlatTree::open(&path) }.expect("mmap flat tree");
+    assert_eq!(flat.find_exact("/api/users"), Some(2));
+    assert_eq!(flat.longest_prefix("/api/posts").unwrap().remainder(), b"/posts");
+    drop(flat);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn iter_in_lexicographic_order() {
+    let mut tree = RadixTree::new().expect("create tree");
+    tree.insert("/b", 2).unwrap();
+    tree.insert("/a/c", 3).unwrap();
+    tree.insert("/a", 1).unwrap();
+
+    let keys: Vec<(Vec<u8>, i32)> = tree.iter().unwrap().collect();
+    assert_eq!(
+        keys,
+        vec![(b"/a".to_vec(), 1), (b"/a/c".to_vec(), 3), (b"/b".to_vec(), 2)]
+    );
+    assert_eq!(RadixTree::new().unwrap().iter().unwrap().count(), 0);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_json_roundtrip() {
+    let mut tree = RadixTree::new().expect("create tree");
+    tree.insert("/api/users", 2).unwrap();
+    tree.insert("/api", 1).unwrap();
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, r#"[["/api",1],["/api/users",2]]"#);
+    let loaded: RadixTree = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.find_exact("/api/users"), Some(2));
+    // Maps of key to index are accepted too.
+    let loaded: RadixTree = serde_json::from_str(r#"{"/api":1,"/api/users":2}"#).unwrap();
+    assert_eq!(loaded.find_exact("/api/users"), Some(2));
+
+    // Keys that are not UTF-8 are byte arrays.
+    let key = [b'/', 0xff, 0xfe];
+    tree.insert(key, 3).unwrap();
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(json, r#"[["/api",1],["/api/users",2],[[47,255,254],3]]"#);
+    let loaded: RadixTree = serde_json::from_str(&json).unwrap();
+    let entries: Vec<_> = loaded.iter().unwrap().collect();
+    assert_eq!(entries[2], (key.to_vec(), 3));
+
+    assert!(serde_json::from_str::<RadixTree>(r#"{"/api": "x"}"#).is_err());
+
+    // The case folding of a tree would be lost.
+    let mut tree = RadixTree::with_case_folding(CaseFolding::Ascii).unwrap();
+    tree.insert("/API", 1).unwrap();
+    assert!(serde_json::to_string(&tree).is_err());
+}
+
+fn route(id: i32, paths: &[&str], hosts: &[&str], methods: &[&str], priority: i32) -> Route {
+    Route {
+        id,
+        paths: paths.iter().map(|s| s.to_string()).collect(),
+        hosts: hosts.iter().map(|s| s.to_string()).collect(),
+        methods: methods.iter().map(|s| s.to_string()).collect(),
+        priority,
+    }
+}
+
+#[test]
+fn router_matching_rules() {
+    let mut router = Router::new().expect("create router");
+    router.add(route(1, &["/*"], &[], &[], 0)).unwrap();
+    router.add(route(2, &["/api/*"], &[], &[], 0)).unwrap();
+    router.add(route(3, &["/api/users"], &[], &["get"], 0)).unwrap();
+    router
+        .add(route(4, &["/api/users"], &["*.Example.com"], &["GET"], 5))
+        .unwrap();
+    router
+        .add(route(5, &["/api/users"], &["api.example.com"], &[], 1))
+        .unwrap();
+
+    let iter = router.create_iter().unwrap();
+    // Exact paths only match themselves, prefix patterns fall back to shorter keys.
+    assert_eq!(router.match_route(&iter, "/api/users", None, Some("GET")), Some(3));
+    assert_eq!(router.match_route(&iter, "/api/users/1", None, Some("GET")), Some(2));
+    assert_eq!(router.match_route(&iter, "/api/users", None, Some("POST")), Some(2));
+    assert_eq!(router.match_route(&iter, "/other", None, None), Some(1));
+    // Highest priority wins among routes accepting the request.
+    assert_eq!(
+        router.match_route(&iter, "/api/users", Some("API.example.com"), Some("GET")),
+        Some(4)
+    );
+    assert_eq!(
+        router.match_route(&iter, "/api/users", Some("api.example.com"), Some("POST")),
+        Some(5)
+    );
+    assert_eq!(
+        router.match_route(&iter, "/api/users", Some("example.com"), Some("GET")),
+        Some(3)
+    );
+
+    assert_eq!(
+        router.add(route(1, &["/x"], &[], &[], 0)),
+        Err(RouteError::DuplicateId(1))
+    );
+    assert_eq!(
+        router.add(route(0, &["/x"], &[], &[], 0)),
+        Err(RouteError::InvalidId(0))
+    );
+    assert_eq!(
+        router.add(route(6, &["/a*b"], &[], &[], 0)),
+        Err(RouteError::InvalidPath("/a*b".to_string()))
+    );
+    assert_eq!(
+        router.add(route(6, &["/x"], &[], &["FETCH"], 0)),
+        Err(RouteError::InvalidMethod("FETCH".to_string()))
+    );
+    // Hosts are ASCII, internationalized names are given in punycode.
+    assert_eq!(
+        router.add(route(6, &["/x"], &["bücher.example"], &[], 0)),
+        Err(RouteError::InvalidHost("bücher.example".to_string()))
+    );
+    assert_eq!(
+        router.add(route(6, &["/api/users"], &[], &["GET"], 0)),
+        Err(RouteError::DuplicatePath("/api/users".to_string()))
+    );
+    assert_eq!(router.routes().len(), 5);
+
+    router
+        .add(route(6, &["/books"], &["XN--Bcher-kva.example"], &[], 0))
+        .unwrap();
+    assert_eq!(router.routes()[5].hosts, vec!["xn--bcher-kva.example"]);
+    let iter = router.create_iter().unwrap();
+    assert_eq!(
+        router.match_route(&iter, "/books", Some("xn--bcher-KVA.example"), None),
+        Some(6)
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn route_config_json_errors_name_the_route() {
+    use super::{ConfigErrorKind, ConfigFormat, RouteConfig};
+
+    let src = r#"{
+  "routes": [
+    { "id": 1, "paths": ["/api/*"] },
+    { "id": 2, "paths": ["/users", "/bad path"] }
+  ]
+}"#;
+    let err = RouteConfig::parse(src, ConfigFormat::Json)
+        .unwrap()
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::InvalidRoute);
+    assert_eq!(err.message(), "routes[1] (id 2): invalid path pattern \"/bad path\"");
+    assert_eq!(err.location(), None);
+
+    let src = r#"{
+  "routes": [
+    { "id": 1, "paths": ["/api/*"] },
+    { "id": 1, "paths": ["/users"] }
+  ]
+}"#;
+    let err = RouteConfig::parse(src, ConfigFormat::Json)
+        .unwrap()
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::Duplicate);
+    assert_eq!(err.to_string(), "routes[1] (id 1): duplicate route id 1");
+
+    let err = RouteConfig::parse(
+        "{\n  \"routes\": [ { \"id\": 1, \"path\": [] } ]\n}",
+        ConfigFormat::Json,
+    )
+    .err()
+    .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::Syntax);
+    assert_eq!(err.location().map(|l| l.line), Some(2));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn route_config_yaml() {
+    use super::{ConfigErrorKind, ConfigFormat, RouteConfig};
+
+    let src =
+        "routes:\n  - id: 1\n    paths: [/api/*]\n  - id: 2\n    paths:\n      - /api/users\n    methods: [GET]\n";
+    let router = RouteConfig::parse(src, ConfigFormat::Yaml).unwrap().build().unwrap();
+    let iter = router.create_iter().unwrap();
+    assert_eq!(router.match_route(&iter, "/api/users", None, Some("GET")), Some(2));
+
+    let src = "routes:\n  - id: 1\n    paths: [/a]\n    hosts: [\"bad host\"]\n";
+    let err = RouteConfig::parse(src, ConfigFormat::Yaml)
+        .unwrap()
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::InvalidRoute);
+    assert_eq!(err.message(), "routes[0] (id 1): invalid host pattern \"bad host\"");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn route_config_toml() {
+    use super::{ConfigErrorKind, ConfigFormat, RouteConfig};
+
+    let src = "[[routes]]\nid = 1\npaths = [\"/api/*\"]\n\n[[routes]]\nid = 2\npaths = [\"/api/*\"]\n";
+    let err = RouteConfig::parse(src, ConfigFormat::Toml)
+        .unwrap()
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::Duplicate);
+    assert_eq!(err.message(), "routes[1] (id 2): duplicate route for path \"/api/*\"");
+
+    let err = RouteConfig::parse("[[routes]]\nid = \"x\"\n", ConfigFormat::Toml)
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), ConfigErrorKind::Syntax);
+    assert_eq!(err.location().map(|l| l.line), Some(2));
+}
+
+#[cfg(feature = "json")]
+fn reloading_router_swaps_on_change(polling: bool) {
+    use super::{ReloadStatus, ReloadingRouter};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("radix_route_matcher_reload_{}_{}", std::process::id(), polling));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("routes.json");
+    std::fs::write(&path, r#"{"routes": [{"id": 1, "paths": ["/v1/*"]}]}"#).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+    let routes = ReloadingRouter::builder(&path)
+        .polling(polling)
+        .poll_interval(Duration::from_millis(20))
+        .on_reload(move |status| {
+            let _ = tx.lock().unwrap().send(matches!(status, ReloadStatus::Reloaded { .. }));
+        })
+        .build()
+        .expect("initial load");
+    let matches = |path: &str| {
+        let router = routes.current();
+        let iter = router.create_iter().unwrap();
+        router.match_route(&iter, path, None, None)
+    };
+    assert_eq!(matches("/v1/users"), Some(1));
+
+    // Replace the file by rename, as deployment tools do.
+    let tmp = dir.join("routes.json.tmp");
+    std::fs::write(
+        &tmp,
+        r#"{"routes": [{"id": 2, "paths": ["/v2/*"]}, {"id": 3, "paths": ["/v3"]}]}"#,
+    )
+    .unwrap();
+    std::fs::rename(&tmp, &path).unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(true));
+    assert_eq!(matches("/v1/users"), None);
+    assert_eq!(matches("/v2/users"), Some(2));
+
+    // An invalid file keeps the last good router.
+    std::fs::write(&path, r#"{"routes": [{"id": 4, "paths": ["no-slash"]}]}"#).unwrap();
+    assert_eq!(rx.recv_timeout(Duration::from_secs(5)), Ok(false));
+    assert_eq!(matches("/v2/users"), Some(2));
+
+    drop(routes);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn reloading_router_with_file_events() {
+    reloading_router_swaps_on_change(false);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn reloading_router_with_polling() {
+    reloading_router_swaps_on_change(true);
+}
+
+#[test]
+fn c_api_iterator_outlives_tree() {
+    use super::c_api::*;
+
+    unsafe {
+        let tree = radix_tree_new();
+        assert!(!tree.is_null());
+        let key = b"/api";
+        assert_eq!(radix_tree_insert(tree, key.as_ptr(), key.len() as _, 1), 1);
+
+        let it = radix_tree_new_it(tree);
+        assert!(!it.is_null());
+        let path = b"/api/users";
+        assert_eq!(radix_tree_search(tree, it, path.as_ptr(), path.len() as _), it);
+        assert_eq!(radix_tree_up(it, path.as_ptr(), path.len() as _), 1);
+
+        // An iterator only searches the tree it was created on.
+        let other = radix_tree_new();
+        assert!(radix_tree_search(other, it, path.as_ptr(), path.len() as _).is_null());
+        assert_eq!(radix_tree_destroy(other), 0);
+
+        // Iterators stay safe to use and release after their tree is gone.
+        let stale = radix_tree_new_it(tree);
+        assert_eq!(radix_tree_destroy(tree), 0);
+        assert_eq!(radix_tree_next(stale, path.as_ptr(), path.len() as _), -1);
+        assert_eq!(radix_tree_up(stale, path.as_ptr(), path.len() as _), -1);
+        assert_eq!(radix_tree_free_it(stale), 0);
+
+        // The legacy stop-then-free sequence keeps working.
+        assert_eq!(radix_tree_stop(it), 0);
+        assert_eq!(radix_tree_prev(it, path.as_ptr(), path.len() as _), -1);
+        libc::free(it as *mut libc::c_void);
+
+        assert_eq!(radix_tree_free_it(std::ptr::null_mut()), 0);
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn c_api_rejects_mismatched_handles() {
+    use super::c_api::*;
+
+    let tree = radix_tree_new();
+    let it = radix_tree_new_it(tree);
+    let key = b"/api";
+    let as_tree = it as *mut radix_tree_t;
+    let as_iter = tree as *mut radix_iter_t;
+
+    assert_eq!(
+        radix_tree_insert(as_tree, key.as_ptr(), key.len() as _, 1),
+        RADIX_TREE_EBADHANDLE
+    );
+    assert!(radix_tree_find(as_tree, key.as_ptr(), key.len() as _).is_null());
+    assert!(radix_tree_new_it(as_tree).is_null());
+    assert_eq!(radix_tree_destroy(as_tree), RADIX_TREE_EBADHANDLE);
+    assert_eq!(
+        radix_tree_up(as_iter, key.as_ptr(), key.len() as _),
+        RADIX_TREE_EBADHANDLE
+    );
+    assert_eq!(radix_tree_free_it(as_iter), RADIX_TREE_EBADHANDLE);
+    assert!(radix_tree_search(tree, as_iter, key.as_ptr(), key.len() as _).is_null());
+
+    // The iterator keeps the destroyed tree handle alive, so misuse is detected.
+    assert_eq!(radix_tree_destroy(tree), 0);
+    assert_eq!(
+        radix_tree_insert(tree, key.as_ptr(), key.len() as _, 1),
+        RADIX_TREE_EBADHANDLE
+    );
+    assert_eq!(radix_tree_destroy(tree), RADIX_TREE_EBADHANDLE);
+    assert_eq!(radix_tree_free_it(it), 0);
+
+    // Destroyed handles are freed once they leave the quarantine.
+    for _ in 0..QUARANTINE_LEN * 2 {
+        let tree = radix_tree_new();
+        assert_eq!(radix_tree_free_it(radix_tree_new_it(tree)), 0);
+        assert_eq!(radix_tree_destroy(tree), 0);
+    }
+    let tree = radix_tree_new();
+    assert_eq!(radix_tree_destroy(tree), 0);
+    assert_eq!(radix_tree_destroy(tree), RADIX_TREE_EBADHANDLE);
+}
+
+#[test]
+fn c_api_reports_last_error() {
+    use super::c_api::*;
+    use std::ffi::CStr;
+
+    unsafe {
+        let tree = radix_tree_new();
+        assert_eq!(radix_tree_insert(tree, std::ptr::null(), 4, 1), RADIX_TREE_EINVAL);
+        let message = CStr::from_ptr(radix_tree_last_error()).to_str().unwrap();
+        assert_eq!(message, "radix_tree_insert: buf is NULL but len is 4");
+
+        // A NULL buffer is a valid empty key.
+        assert_eq!(radix_tree_insert(tree, std::ptr::null(), 0, 7), 1);
+        assert_eq!(radix_tree_find(tree, std::ptr::null(), 0), 7 as *mut libc::c_void);
+
+        assert!(radix_tree_new_it(std::ptr::null_mut()).is_null());
+        let message = CStr::from_ptr(radix_tree_last_error()).to_str().unwrap();
+        assert_eq!(message, "radix_tree_new_it: tree is NULL");
+
+        // Errors are per thread.
+        std::thread::spawn(|| assert!(radix_tree_last_error().is_null()))
+            .join()
+            .unwrap();
+        radix_tree_destroy(tree);
+    }
+}
+
+#[test]
+fn c_api_foreach() {
+    use super::c_api::*;
+    use libc::{c_int, c_uchar, c_ulong, c_void};
+
+    #[derive(Default)]
+    struct Visit {
+        seen: Vec<(Vec<u8>, i32)>,
+        stop_at: i32,
+    }
+
+    unsafe extern "C" fn collect(key: *const c_uchar, len: c_ulong, value: c_int, userdata: *mut c_void) -> c_int {
+        let visit = &mut *(userdata as *mut Visit);
+        visit
+            .seen
+            .push((std::slice::from_raw_parts(key, len as usize).to_vec(), value));
+        (value == visit.stop_at) as c_int
+    }
+
+    let tree = radix_tree_new();
+    for (i, key) in ["/", "/api", "/api/users", "/apix", "/static"].iter().enumerate() {
+        radix_tree_insert(tree, key.as_ptr(), key.len() as _, i as i32 + 1);
+    }
+    let mut visit = Visit::default();
+    let userdata = &mut visit as *mut Visit as *mut c_void;
+
+    let path = b"/api/users/1";
+    assert_eq!(
+        radix_tree_foreach_prefix(tree, path.as_ptr(), path.len() as _, Some(collect), userdata),
+        3
+    );
+    assert_eq!(
+        visit.seen,
+        [(b"/api/users".to_vec(), 3), (b"/api".to_vec(), 2), (b"/".to_vec(), 1)]
+    );
+
+    visit.seen.clear();
+    assert_eq!(
+        radix_tree_foreach(tree, b"/api".as_ptr(), 4, Some(collect), userdata),
+        3
+    );
+    assert_eq!(
+        visit.seen,
+        [
+            (b"/api".to_vec(), 2),
+            (b"/api/users".to_vec(), 3),
+            (b"/apix".to_vec(), 4)
+        ]
+    );
+
+    // Returning non-zero stops the iteration.
+    visit.seen.clear();
+    visit.stop_at = 2;
+    assert_eq!(
+        radix_tree_foreach(tree, std::ptr::null(), 0, Some(collect), userdata),
+        2
+    );
+    assert_eq!(visit.seen, [(b"/".to_vec(), 1), (b"/api".to_vec(), 2)]);
+
+    assert_eq!(
+        radix_tree_foreach(tree, std::ptr::null(), 0, None, userdata),
+        RADIX_TREE_EINVAL
+    );
+    radix_tree_destroy(tree);
+}
+
+#[test]
+fn batch_insert_and_match() {
+    let mut tree = RadixTree::new().unwrap();
+    tree.insert_many([("/", 1), ("/api", 2), ("/api/users", 3)]).unwrap();
+    let iter = tree.create_iter().unwrap();
+    let mut results = vec![None; 3];
+    assert_eq!(
+        tree.match_many(&iter, &["/api/users/1", "/apix", "/api"], &mut results),
+        3
+    );
+    let remainders: Vec<_> = results.iter().map(|m| m.as_ref().map(|m| m.remainder())).collect();
+    assert_eq!(remainders, [Some(&b"/1"[..]), Some(b"x"), Some(b"")]);
+
+    use super::c_api::*;
+
+    let t = radix_tree_new();
+    let keys = [b"/api".as_ptr(), b"/api/users".as_ptr(), b"/api".as_ptr()];
+    let lens = [4, 10, 4];
+    assert_eq!(
+        radix_tree_insert_many(t, keys.as_ptr(), lens.as_ptr(), [1, 2, 3].as_ptr(), 3),
+        2
+    );
+
+    let paths = [b"/api/users/1".as_ptr(), b"/static".as_ptr(), std::ptr::null()];
+    let lens = [12, 7, 0];
+    let mut results = [0; 3];
+    assert_eq!(
+        radix_tree_match_many(t, paths.as_ptr(), lens.as_ptr(), 3, results.as_mut_ptr()),
+        1
+    );
+    assert_eq!(results, [2, -1, -1]);
+
+    // Invalid batches are rejected before any insertion.
+    let keys = [b"/new".as_ptr(), std::ptr::null()];
+    assert_eq!(
+        radix_tree_insert_many(t, keys.as_ptr(), [4, 1].as_ptr(), [1, 2].as_ptr(), 2),
+        RADIX_TREE_EINVAL
+    );
+    assert!(radix_tree_find(t, b"/new".as_ptr(), 4).is_null());
+    radix_tree_destroy(t);
+}
+
+#[test]
+fn c_api_versions() {
+    use super::c_api::*;
+    use std::ffi::CStr;
+
+    unsafe {
+        assert_eq!(radix_tree_abi_version(), RADIX_TREE_ABI_VERSION);
+        let version = CStr::from_ptr(radix_tree_version_string()).to_str().unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+        let features = radix_tree_features();
+        assert_eq!(features & RADIX_TREE_FEATURE_HANDLE_CHECKS != 0, cfg!(debug_assertions));
+        assert_eq!(features & RADIX_TREE_FEATURE_JSON != 0, cfg!(feature = "json"));
+    }
+}
+
+#[test]
+fn tree_memory_is_accounted() {
+    let mut tree = RadixTree::new().unwrap();
+    let empty = tree.allocated_bytes();
+    assert!(empty > 0);
+
+    let paths: Vec<String> = (0..200).map(|i| format!("/api/v{}/resource/{}", i % 3, i)).collect();
+    let cycle = |tree: &mut RadixTree| {
+        for (i, path) in paths.iter().enumerate() {
+            tree.insert(path, i as i32 + 1).unwrap();
+        }
+        let full = tree.allocated_bytes();
+        for path in &paths {
+            tree.remove(path).unwrap();
+        }
+        full
+    };
+
+    let full = cycle(&mut tree);
+    assert!(full > empty);
+    // rax keeps an emptied head node allocated, but nothing else may be left behind.
+    let drained = tree.allocated_bytes();
+    assert!(drained < full);
+    assert_eq!(cycle(&mut tree), full);
+    assert_eq!(tree.allocated_bytes(), drained);
+
+    // A tree loaded from a snapshot accounts for its nodes too.
+    tree.insert("/api/users", 1).unwrap();
+    let mut buf = Vec::new();
+    tree.write_snapshot(&mut buf).unwrap();
+    let loaded = RadixTree::read_snapshot(&buf[..]).unwrap();
+    assert!(loaded.allocated_bytes() > empty);
+
+    use super::c_api::*;
+
+    let t = radix_tree_new();
+    let mut bytes = 0;
+    assert_eq!(radix_tree_allocated_bytes(t, &mut bytes), 0);
+    assert!(bytes > 0);
+    assert_eq!(radix_tree_allocated_bytes(t, std::ptr::null_mut()), RADIX_TREE_EINVAL);
+    radix_tree_destroy(t);
+}
+
+#[test]
+fn tree_stats() {
+    let mut tree = RadixTree::new().unwrap();
+    let stats = tree.stats();
+    assert_eq!((stats.keys, stats.nodes, stats.leaf_nodes), (0, 1, 1));
+    assert_eq!(stats.max_depth(), None);
+    assert_eq!(stats.memory.total(), tree.allocated_bytes());
+
+    for i in 0..100 {
+        tree.insert(format!("/api/v{}/items/{}", i % 4, i), i + 1).unwrap();
+    }
+    tree.insert("/api", 1000).unwrap();
+    let stats = tree.stats();
+    assert_eq!(stats.keys, tree.len());
+    assert_eq!(stats.nodes, tree.node_count());
+    assert_eq!(
+        stats.compressed_nodes + stats.branching_nodes + stats.leaf_nodes,
+        stats.nodes
+    );
+    assert_eq!(stats.depth_histogram.iter().sum::<usize>(), stats.keys);
+    assert!(stats.average_fan_out > 1.0);
+    assert_eq!(stats.memory.total(), tree.allocated_bytes());
+    assert_eq!(tree.memory_usage(), stats.memory);
+
+    // Snapshots preserve the node layout, so the shape is the same.
+    let mut buf = Vec::new();
+    tree.write_snapshot(&mut buf).unwrap();
+    let loaded = RadixTree::read_snapshot(&buf[..]).unwrap();
+    let loaded_stats = loaded.stats();
+    assert_eq!(loaded_stats.depth_histogram, stats.depth_histogram);
+    assert_eq!(loaded_stats.nodes, stats.nodes);
+    assert_eq!(loaded_stats.memory.total(), loaded.allocated_bytes());
+}
+
+#[test]
+fn tree_dumps() {
+    let mut tree = RadixTree::new().unwrap();
+    tree.insert_many([("/api", 1), ("/api/users", 2), ("/api/posts", 3)])
+        .unwrap();
+
+    let dump = tree.debug_dump();
+    assert_eq!(
+        dump.to_string(),
+        concat!(
+            "\"/api\"\n",
+            "  [/] key=\"/api\" = 1\n",
+            "    '/' -> [pu]\n",
+            "      'p' -> \"osts\"\n",
+            "        [] key=\"/api/posts\" = 3\n",
+            "      'u' -> \"sers\"\n",
+            "        [] key=\"/api/users\" = 2\n",
+        )
+    );
+    let users = &dump.children[0].children[0].children[1].children[0];
+    assert_eq!(users.kind, crate::NodeKind::Leaf);
+    assert_eq!(users.prefix, b"/api/users");
+
+    let dot = tree.to_dot();
+    assert!(dot.starts_with("digraph radix_tree {\n"));
+    assert!(dot.contains("  n0 [shape=box, peripheries=1, label=\"\\\"/api\\\"\"];\n"));
+    assert!(dot.contains("  n1 [shape=ellipse, peripheries=2, label=\"[/]\\n= 1\"];\n"));
+    assert!(dot.contains("  n1 -> n2 [label=\"/\"];\n"));
+    assert!(dot.contains("  n2 -> n3 [label=\"p\"];\n"));
+    assert_eq!(dot.matches(" -> ").count(), tree.node_count() - 1);
+    assert!(dot.ends_with("}\n"));
+}
+
+#[test]
+fn binary_keys() {
+    let mut tree = RadixTree::new().unwrap();
+    // Raw request target, not valid UTF-8.
+    tree.insert(b"/files/\xff\xfe", 1).unwrap();
+    tree.insert(vec![0u8, 1, 2], 2).unwrap();
+    tree.insert(String::from("/files"), 3).unwrap();
+    tree.insert_many([(&b"\x00\x01"[..], 4), (&b"\x00\x01\x02\x03"[..], 5)])
+        .unwrap();
+
+    assert_eq!(tree.find_exact(&[0xffu8][..]), None);
+    assert_eq!(tree.find_exact(b"/files/\xff\xfe"), Some(1));
+    assert_eq!(tree.find_exact([0u8, 1, 2]), Some(2));
+    assert_eq!(tree.find_exact("/files"), Some(3));
+
+    let iter = tree.create_iter().unwrap();
+    assert_eq!(tree.longest_prefix(&iter, b"/files/\xff\xfe/x").unwrap().value, 1);
+    assert_eq!(
+        tree.longest_prefix(&iter, b"/files/\xff").unwrap().remainder(),
+        b"/\xff"
+    );
+    let values: Vec<i32> = tree
+        .find_all_prefixes(&iter, &[0u8, 1, 2, 3, 4])
+        .iter()
+        .map(|m| m.value)
+        .collect();
+    assert_eq!(values, vec![5, 2, 4]);
+
+    let paths: [&[u8]; 3] = [b"\x00\x01\x02\x09", b"/files/a", b"\xff"];
+    let mut results = vec![None; 3];
+    assert_eq!(tree.match_many(&iter, &paths, &mut results), 2);
+    let values: Vec<_> = results.into_iter().map(|m| m.map(|m| m.value)).collect();
+    assert_eq!(values, [Some(2), Some(3), None]);
+
+    tree.remove(vec![0u8, 1, 2]).unwrap();
+    assert_eq!(tree.find_exact([0u8, 1, 2]), None);
+
+    let mut buf = Vec::new();
+    tree.write_flat(&mut buf).unwrap();
+    let flat = FlatTree::new(buf).unwrap();
+    assert_eq!(flat.find_exact(b"/files/\xff\xfe"), Some(1));
+    assert_eq!(flat.longest_prefix(&[0u8, 1, 2, 3, 4]).unwrap().value, 5);
+}
+
+#[test]
+fn ip_prefix_parsing() {
+    let prefix: IpPrefix = "192.168.1.77/20".parse().unwrap();
+    assert_eq!(prefix.to_string(), "192.168.0.0/20");
+    assert_eq!(prefix.prefix_len(), 20);
+    assert!(prefix.contains("192.168.15.255".parse().unwrap()));
+    assert!(!prefix.contains("192.168.16.0".parse().unwrap()));
+    assert!(!prefix.contains("::ffff:192.168.1.1".parse().unwrap()));
+
+    assert_eq!(
+        "2001:db8::1".parse::<IpPrefix>().unwrap().to_string(),
+        "2001:db8::1/128"
+    );
+    assert_eq!(
+        "2001:db8:ffff::/33".parse::<IpPrefix>().unwrap().to_string(),
+        "2001:db8:8000::/33"
+    );
+    assert_eq!("0.0.0.0/0".parse::<IpPrefix>().unwrap().prefix_len(), 0);
+
+    for bad in [
+        "10.0.0.0/",
+        "10.0.0.0/+8",
+        "10.0.0.0/08",
+        "10.0.0.0/008",
+        "10.0.0.0/00",
+        "10.0.0.0/0008",
+        "10.0.0/8",
+        "host/8",
+        "::/x",
+    ] {
+        assert_eq!(
+            bad.parse::<IpPrefix>(),
+            Err(IpPrefixError::InvalidPrefix(bad.to_string())),
+            "{}",
+            bad
+        );
+    }
+    assert_eq!("10.0.0.0/33".parse::<IpPrefix>(), Err(IpPrefixError::InvalidLength(33)));
+    assert_eq!("::/129".parse::<IpPrefix>(), Err(IpPrefixError::InvalidLength(129)));
+}
+
+#[test]
+fn ip_prefix_table_matches() {
+    let p = |s: &str| s.parse::<IpPrefix>().unwrap();
+    let a = |s: &str| s.parse::<std::net::IpAddr>().unwrap();
+
+    let mut table = IpPrefixTable::new().unwrap();
+    assert!(table.is_empty());
+    table.insert(p("0.0.0.0/0"), "v4 default").unwrap();
+    table.insert(p("10.0.0.0/8"), "ten").unwrap();
+    table.insert(p("10.128.0.0/9"), "ten upper half").unwrap();
+    table.insert(p("10.128.0.1"), "host").unwrap();
+    table.insert(p("2001:db8::/32"), "doc").unwrap();
+    table.insert(p("2001:db8:0:1::/64"), "doc subnet").unwrap();
+    assert_eq!(table.insert(p("10.0.0.0/8"), "10/8").unwrap(), Some("ten"));
+    assert_eq!(table.len(), 6);
+
+    let longest = |table: &IpPrefixTable<&'static str>, addr: &str| {
+        table
+            .longest_match(a(addr))
+            .map(|(prefix, value)| (prefix.to_string(), *value))
+    };
+    assert_eq!(longest(&table, "10.127.255.255"), Some(("10.0.0.0/8".into(), "10/8")));
+    assert_eq!(
+        longest(&table, "10.128.0.0"),
+        Some(("10.128.0.0/9".into(), "ten upper half"))
+    );
+    assert_eq!(longest(&table, "10.128.0.1"), Some(("10.128.0.1/32".into(), "host")));
+    assert_eq!(longest(&table, "11.0.0.1"), Some(("0.0.0.0/0".into(), "v4 default")));
+    assert_eq!(
+        longest(&table, "2001:db8:0:1::42"),
+        Some(("2001:db8:0:1::/64".into(), "doc subnet"))
+    );
+    assert_eq!(longest(&table, "2001:db8:1::"), Some(("2001:db8::/32".into(), "doc")));
+    // The IPv4 default route does not cover IPv6, even IPv4-mapped, addresses.
+    assert_eq!(longest(&table, "::ffff:10.0.0.1"), None);
+
+    let all: Vec<_> = table
+        .all_matches(a("10.128.0.1"))
+        .into_iter()
+        .map(|(_, v)| *v)
+        .collect();
+    assert_eq!(all, vec!["host", "ten upper half", "10/8", "v4 default"]);
+
+    assert_eq!(table.get(&p("10.128.0.0/9")), Some(&"ten upper half"));
+    assert_eq!(table.remove(&p("10.128.0.0/9")), Some("ten upper half"));
+    assert_eq!(table.remove(&p("10.128.0.0/9")), None);
+    assert_eq!(longest(&table, "10.128.0.0"), Some(("10.0.0.0/8".into(), "10/8")));
+    // The freed slot is reused.
+    table.insert(p("172.16.0.0/12"), "private").unwrap();
+    assert_eq!(longest(&table, "172.31.0.1"), Some(("172.16.0.0/12".into(), "private")));
+    assert_eq!(table.len(), 6);
+
+    let order: Vec<_> = table.iter().map(|(prefix, _)| prefix.to_string()).collect();
+    assert_eq!(
+        order,
+        vec![
+            "0.0.0.0/0",
+            "10.0.0.0/8",
+            "10.128.0.1/32",
+            "172.16.0.0/12",
+            "2001:db8::/32",
+            "2001:db8:0:1::/64"
+        ]
+    );
+}
+
+#[test]
+fn ip_prefix_table_aggregation() {
+    let p = |s: &str| s.parse::<IpPrefix>().unwrap();
+
+    let mut table = IpPrefixTable::new().unwrap();
+    for (prefix, value) in [
+        ("10.0.0.0/8", 1),
+        // Redundant with 10.0.0.0/8.
+        ("10.1.0.0/16", 1),
+        // Halves of 192.168.0.0/23 with equal values, and a different host inside.
+        ("192.168.0.0/24", 2),
+        ("192.168.1.0/25", 2),
+        ("192.168.1.128/25", 2),
+        ("192.168.1.1/32", 3),
+        // Different values: kept apart.
+        ("172.16.0.0/13", 4),
+        ("172.24.0.0/13", 5),
+        ("2001:db8::/33", 6),
+        ("2001:db8:8000::/33", 6),
+    ] {
+        table.insert(p(prefix), value).unwrap();
+    }
+
+    // Longest matches of a spread of addresses, before aggregation.
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut addrs: Vec<std::net::IpAddr> = Vec::new();
+    for _ in 0..2000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let base = [0x0a00_0000u32, 0xc0a8_0000, 0xac10_0000][state as usize % 3];
+        addrs.push(std::net::Ipv4Addr::from(base + (state >> 40) as u32 % 0x0010_0000).into());
+        addrs.push(std::net::Ipv6Addr::from(0x2001_0db8_u128 << 96 | (state as u128) << 48).into());
+    }
+    let before: Vec<_> = addrs
+        .iter()
+        .map(|addr| table.longest_match(*addr).map(|(_, v)| *v))
+        .collect();
+
+    assert_eq!(table.aggregate().unwrap(), 4);
+    let prefixes: Vec<_> = table
+        .iter()
+        .map(|(prefix, value)| (prefix.to_string(), *value))
+        .collect();
+    assert_eq!(
+        prefixes,
+        vec![
+            ("10.0.0.0/8".to_string(), 1),
+            ("172.16.0.0/13".to_string(), 4),
+            ("172.24.0.0/13".to_string(), 5),
+            ("192.168.0.0/23".to_string(), 2),
+            ("192.168.1.1/32".to_string(), 3),
+            ("2001:db8::/32".to_string(), 6),
+        ]
+    );
+    let after: Vec<_> = addrs
+        .iter()
+        .map(|addr| table.longest_match(*addr).map(|(_, v)| *v))
+        .collect();
+    assert_eq!(before, after);
+    assert_eq!(table.aggregate().unwrap(), 0);
+}
+
+#[test]
+fn domain_set_rules() {
+    let mut set = DomainSet::new().unwrap();
+    assert!(set.is_empty());
+    set.insert(".corp", 1).unwrap();
+    set.insert(".internal.corp", 2).unwrap();
+    set.insert("internal.corp", 3).unwrap();
+    set.insert("Blocked.Example.COM.", 4).unwrap();
+    assert_eq!(set.insert("blocked.example.com", 5).unwrap(), Some(4));
+    assert_eq!(set.len(), 4);
+
+    let value = |set: &DomainSet<i32>, host: &str| set.lookup(host).map(|(_, v)| *v);
+    // The exact rule beats the subdomain rule for the same name.
+    assert_eq!(value(&set, "internal.corp"), Some(3));
+    assert_eq!(value(&set, "db.internal.corp"), Some(2));
+    assert_eq!(value(&set, "a.b.internal.corp"), Some(2));
+    assert_eq!(value(&set, "internal2.corp"), Some(1));
+    assert_eq!(value(&set, "corp"), Some(1));
+    assert_eq!(value(&set, "blocked.example.com"), Some(5));
+    assert_eq!(value(&set, "www.blocked.example.com"), None);
+    assert_eq!(value(&set, "example.com"), None);
+    assert_eq!(value(&set, "xcorp"), None);
+    assert_eq!(set.lookup("DB.Internal.Corp"), Some((".internal.corp", &2)));
+
+    for rule in [
+        "",
+        ".",
+        "a..b",
+        "-_-.",
+        "bad host.com",
+        "*.example.com",
+        &"a".repeat(64),
+    ] {
+        assert!(
+            matches!(set.insert(rule, 0), Err(DomainError::InvalidName(_))),
+            "{:?}",
+            rule
+        );
+    }
+    assert_eq!(set.lookup(&"a.".repeat(127)), None);
+
+    assert_eq!(set.get(".internal.corp"), Some(&2));
+    assert_eq!(set.remove("internal.corp"), Some(3));
+    assert_eq!(set.remove("internal.corp"), None);
+    assert_eq!(value(&set, "internal.corp"), Some(2));
+    assert_eq!(set.len(), 3);
+    let mut rules: Vec<_> = set.iter().map(|(rule, _)| rule).collect();
+    rules.sort();
+    assert_eq!(rules, vec![".corp", ".internal.corp", "blocked.example.com"]);
+}
+
+#[test]
+fn domain_set_loading() {
+    let hosts = "\
+# Blocklist
+127.0.0.1 localhost
+0.0.0.0 0.0.0.0
+0.0.0.0 ads.example.com  Tracker.Example.NET # trailing comment
+
+::1 ip6-localhost
+";
+    let mut set = DomainSet::new().unwrap();
+    assert_eq!(set.load(hosts.as_bytes(), ListFormat::Hosts, "block").unwrap(), 4);
+    assert!(set.contains("tracker.example.net"));
+    assert!(set.contains("ip6-localhost"));
+    assert!(!set.contains("cdn.ads.example.com"));
+
+    let plain = "internal.corp\n.internal.corp\n  # comment\n\nads.example.com\n";
+    assert_eq!(set.load(plain.as_bytes(), ListFormat::Plain, "direct").unwrap(), 3);
+    assert_eq!(set.len(), 6);
+    assert_eq!(set.lookup("ads.example.com").map(|(_, v)| *v), Some("direct"));
+
+    let err = set
+        .load("ok.example.com\nnot valid\n".as_bytes(), ListFormat::Plain, "x")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        DomainError::InvalidLine {
+            line: 2,
+            content: "not valid".to_string()
+        }
+    );
+    assert!(set.contains("ok.example.com"));
+    let err = set
+        .load("example.com 127.0.0.1\n".as_bytes(), ListFormat::Hosts, "x")
+        .unwrap_err();
+    assert!(matches!(err, DomainError::InvalidLine { line: 1, .. }));
+}
+
+#[test]
+fn domain_set_idna() {
+    let mut set = DomainSet::new().unwrap();
+    set.insert(".xn--bcher-kva.example", 1).unwrap();
+    if cfg!(feature = "idna") {
+        set.insert("Bücher.example", 2).unwrap();
+        assert_eq!(set.lookup("BÜCHER.example"), Some(("xn--bcher-kva.example", &2)));
+        assert_eq!(set.lookup("shop.bücher.example").map(|(_, v)| *v), Some(1));
+    } else {
+        assert!(matches!(
+            set.insert("bücher.example", 2),
+            Err(DomainError::InvalidName(_))
+        ));
+        assert_eq!(set.lookup("shop.bücher.example"), None);
+    }
+    assert_eq!(set.lookup("shop.xn--bcher-kva.example").map(|(_, v)| *v), Some(1));
+}
+
+#[test]
+fn topic_matcher_wildcards() {
+    let mut matcher = TopicMatcher::new().unwrap();
+    for (i, filter) in [
+        "sport/tennis/player1",
+        "sport/tennis/+",
+        "sport/#",
+        "sport/+",
+        "+/+",
+        "/+",
+        "+",
+        "#",
+        "+/tennis/#",
+        "$SYS/#",
+        "$SYS/+/load",
+        "sport/tennis/player1/#",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        assert_eq!(matcher.insert(filter, i).unwrap(), None);
+    }
+    assert_eq!(matcher.len(), 12);
+
+    let matched = |matcher: &TopicMatcher<usize>, topic: &str| {
+        let mut filters: Vec<_> = matcher.matches(topic).into_iter().map(|(f, _)| f.to_string()).collect();
+        filters.sort();
+        filters
+    };
+    assert_eq!(
+        matched(&matcher, "sport/tennis/player1"),
+        vec![
+            "#",
+            "+/tennis/#",
+            "sport/#",
+            "sport/tennis/+",
+            "sport/tennis/player1",
+            "sport/tennis/player1/#"
+        ]
+    );
+    // `#` also matches the parent level, and `+` matches an empty level.
+    assert_eq!(matched(&matcher, "sport"), vec!["#", "+", "sport/#"]);
+    assert_eq!(matched(&matcher, "sport/"), vec!["#", "+/+", "sport/#", "sport/+"]);
+    assert_eq!(matched(&matcher, "/finance"), vec!["#", "+/+", "/+"]);
+    assert_eq!(
+        matched(&matcher, "sport/tennis"),
+        vec!["#", "+/+", "+/tennis/#", "sport/#", "sport/+"]
+    );
+    assert_eq!(matched(&matcher, "sports"), vec!["#", "+"]);
+    // Topics starting with `$` are not matched by a leading wildcard.
+    assert_eq!(matched(&matcher, "$SYS/broker/load"), vec!["$SYS/#", "$SYS/+/load"]);
+    assert_eq!(matched(&matcher, "$SYS"), vec!["$SYS/#"]);
+    assert_eq!(matched(&matcher, "$other/tennis/x"), Vec::<String>::new());
+    // Wildcards are not allowed in published topics.
+    assert!(matcher.matches("sport/+").is_empty());
+    assert!(!matcher.has_match(""));
+
+    for filter in ["", "sport/#/x", "sport#", "sport/ten+", "##", "a\0b"] {
+        assert_eq!(
+            matcher.insert(filter, 0),
+            Err(TopicError::InvalidFilter(filter.to_string()))
+        );
+    }
+
+    assert_eq!(matcher.get("sport/#"), Some(&2));
+    assert_eq!(matcher.remove("#"), Some(7));
+    assert_eq!(matcher.remove("#"), None);
+    assert_eq!(matched(&matcher, "sports"), vec!["+"]);
+    assert_eq!(matcher.insert("sport/+", 20).unwrap(), Some(3));
+    assert_eq!(matcher.iter().count(), 11);
+}
+
+#[test]
+fn glob_router_matches() {
+    let mut router = GlobRouter::new().unwrap();
+    for (i, pattern) in [
+        "/repos/*/settings/**",
+        "/repos/public/settings/**",
+        "/repos/**",
+        "**",
+        "/repos/?/settings",
+        "/repos/**/settings",
+        "*.rs",
+        "/files/\\*",
+        "/files/*",
+        "",
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        assert_eq!(router.insert(pattern, i).unwrap(), None);
+    }
+    assert_eq!(router.len(), 10);
+
+    let matched = |router: &GlobRouter<usize>, path: &str| -> Vec<String> {
+        router.matches(path).into_iter().map(|(p, _)| p.to_string()).collect()
+    };
+    assert_eq!(
+        matched(&router, "/repos/public/settings/hooks"),
+        vec!["/repos/public/settings/**", "/repos/*/settings/**", "/repos/**", "**"]
+    );
+    // `**/` matches zero or more segments, `?` one character of a segment.
+    assert_eq!(
+        matched(&router, "/repos/a/settings"),
+        vec!["/repos/?/settings", "/repos/**/settings", "/repos/**", "**"]
+    );
+    assert_eq!(
+        matched(&router, "/repos/settings"),
+        vec!["/repos/**/settings", "/repos/**", "**"]
+    );
+    assert_eq!(
+        matched(&router, "/repos/é/settings"),
+        vec!["/repos/?/settings", "/repos/**/settings", "/repos/**", "**"]
+    );
+    assert_eq!(
+        matched(&router, "/repos/ab/settings/"),
+        vec!["/repos/*/settings/**", "/repos/**", "**"]
+    );
+    // `*` does not cross `/`, and patterns match whole paths.
+    assert_eq!(matched(&router, "main.rs"), vec!["*.rs", "**"]);
+    assert_eq!(matched(&router, "src/main.rs"), vec!["**"]);
+    assert_eq!(matched(&router, "main.rs.bak"), vec!["**"]);
+    assert_eq!(matched(&router, "/files/*"), vec!["/files/\\*", "/files/*", "**"]);
+    assert_eq!(matched(&router, "/files/a"), vec!["/files/*", "**"]);
+    assert_eq!(matched(&router, ""), vec!["", "**"]);
+
+    // Pathological patterns stay fast.
+    router.insert("/a*a*a*a*a*a*a*a*a*a*b", 99).unwrap();
+    assert_eq!(matched(&router, &format!("/{}", "a".repeat(200))), vec!["**"]);
+
+    for pattern in ["/a/***", "/a\\"] {
+        assert_eq!(
+            router.insert(pattern, 0),
+            Err(GlobError::InvalidPattern(pattern.to_string()))
+        );
+    }
+    assert_eq!(router.get("/repos/**"), Some(&2));
+    assert_eq!(router.insert("/repos/**", 20).unwrap(), Some(2));
+    assert_eq!(router.remove("**"), Some(3));
+    assert_eq!(router.remove("**"), None);
+    assert_eq!(router.remove(""), Some(9));
+    assert_eq!(router.best_match("/repos/x"), Some(("/repos/**", &20)));
+    assert_eq!(router.best_match("main.rs"), Some(("*.rs", &6)));
+    assert_eq!(router.iter().count(), router.len());
+    assert_eq!(router.len(), 9);
+}
+
+#[test]
+fn find_matching_agrees_with_glob_router() {
+    let mut tree = RadixTree::new().unwrap();
+    let keys = [
+        "",
+        "/",
+        "/api",
+        "/api/",
+        "/api/users",
+        "/api/v1/users",
+        "/api/v1/users/me",
+        "/api/v2/users",
+        "/api/v2/posts",
+        "/api/v2/users/é",
+        "/api/é/users",
+        "/apix/users",
+        "/admin/users",
+        "/admin/settings/keys",
+        "/static/app.js",
+        "/static/css/app.css",
+        "users",
+    ];
+    for (i, key) in keys.iter().enumerate() {
+        tree.insert(key, i as i32 + 1).unwrap();
+    }
+
+    for pattern in [
+        "/api/*/users",
+        "/api/?/users",
+        "/api/v?/users",
+        "/api/**",
+        "/api/**/users",
+        "/**/users",
+        "**/users",
+        "**",
+        "*",
+        "/api*",
+        "/api/v2/users/?",
+        "/static/**.css",
+        "/static/*.js",
+        "/a*/*s",
+        "",
+        "/",
+        "/nothing/**",
+    ] {
+        let mut router = GlobRouter::new().unwrap();
+        router.insert(pattern, ()).unwrap();
+        let expected: Vec<_> = tree
+            .iter()
+            .unwrap()
+            .filter(|(key, _)| !router.matches(std::str::from_utf8(key).unwrap()).is_empty())
+            .collect();
+        let found: Vec<_> = tree.find_matching(pattern).unwrap().collect();
+        assert_eq!(found, expected, "{:?}", pattern);
+    }
+    assert_eq!(
+        tree.find_matching("/api/*/users")
+            .unwrap()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>(),
+        vec![6, 8, 11]
+    );
+    assert!(matches!(
+        tree.find_matching("/a/***"),
+        Err(GlobError::InvalidPattern(_))
+    ));
+}
+
+#[test]
+fn completions() {
+    let mut tree = RadixTree::new().unwrap();
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut keys = std::collections::BTreeMap::new();
+    for i in 1..=300 {
+        let r = next();
+        let key = format!(
+            "/{}/{}/{}",
+            ["api", "app", "admin"][r as usize % 3],
+            r % 7,
+            (r >> 8) % 1000
+        );
+        tree.insert(&key, i).unwrap();
+        keys.insert(key, i);
+    }
+    let mut weights = std::collections::HashMap::new();
+    for (n, key) in keys.keys().enumerate() {
+        if n % 3 != 0 {
+            let weight = next() % 50;
+            assert!(tree.set_weight(key, weight));
+            weights.insert(key.clone(), weight);
+        }
+    }
+    assert!(!tree.set_weight("/missing", 1));
+    assert_eq!(tree.weight("/missing"), None);
+
+    let check = |tree: &RadixTree,
+                 keys: &std::collections::BTreeMap<String, i32>,
+                 weights: &std::collections::HashMap<String, u64>| {
+        for prefix in ["", "/", "/a", "/ap", "/app/", "/api/3", "/admin/1/", "/x"] {
+            for k in [0, 1, 5, 1000] {
+                let matching = keys.iter().filter(|(key, _)| key.starts_with(prefix));
+                let expected: Vec<_> = matching
+                    .clone()
+                    .take(k)
+                    .map(|(key, v)| (key.clone().into_bytes(), *v))
+                    .collect();
+                assert_eq!(tree.complete(prefix, k), expected, "{:?} {}", prefix, k);
+
+                let mut by_weight: Vec<_> = matching.collect();
+                by_weight.sort_by_key(|(key, _)| std::cmp::Reverse(weights.get(*key).copied().unwrap_or(0)));
+                let expected: Vec<_> = by_weight
+                    .into_iter()
+                    .take(k)
+                    .map(|(key, v)| (key.clone().into_bytes(), *v))
+                    .collect();
+                assert_eq!(tree.complete_by_weight(prefix, k), expected, "{:?} {}", prefix, k);
+            }
+        }
+    };
+    check(&tree, &keys, &weights);
+
+    // Updates invalidate the cached weights.
+    let removed: Vec<String> = keys.keys().step_by(4).cloned().collect();
+    for key in &removed {
+        tree.remove(key).unwrap();
+        keys.remove(key);
+        weights.remove(key);
+    }
+    let heavy = keys.keys().nth(10).unwrap().clone();
+    tree.set_weight(&heavy, 1000);
+    weights.insert(heavy.clone(), 1000);
+    tree.insert("/app/new", 999).unwrap();
+    keys.insert("/app/new".to_string(), 999);
+    tree.insert(&removed[0], 998).unwrap();
+    keys.insert(removed[0].clone(), 998);
+    assert_eq!(tree.weight(&removed[0]), Some(0));
+    check(&tree, &keys, &weights);
+    assert_eq!(tree.complete_by_weight("", 1)[0].0, heavy.as_bytes());
+}
+
+#[test]
+fn fuzzy_find_agrees_with_levenshtein() {
+    fn levenshtein(a: &[u8], b: &[u8]) -> usize {
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+        for (i, &x) in a.iter().enumerate() {
+            let mut next = vec![i + 1];
+            for (j, &y) in b.iter().enumerate() {
+                next.push((row[j] + (x != y) as usize).min(row[j + 1] + 1).min(next[j] + 1));
+            }
+            row = next;
+        }
+        row[b.len()]
+    }
+
+    let mut tree = RadixTree::new().unwrap();
+    let keys = [
+        "",
+        "/",
+        "/api",
+        "/api/users",
+        "/api/user",
+        "/api/users/me",
+        "/api/posts",
+        "/apl/users",
+        "/admin/users",
+        "/users",
+        "/api/usres",
+        "/static/app.js",
+    ];
+    for (i, key) in keys.iter().enumerate() {
+        tree.insert(key, i as i32 + 1).unwrap();
+    }
+    for path in [
+        "/api/users",
+        "/api/usr",
+        "api/users",
+        "/",
+        "",
+        "/static/app.css",
+        "/xyz",
+    ] {
+        for max_distance in 0..4 {
+            let mut expected: Vec<_> = keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| {
+                    (
+                        key.as_bytes().to_vec(),
+                        i as i32 + 1,
+                        levenshtein(key.as_bytes(), path.as_bytes()),
+                    )
+                })
+                .filter(|(_, _, distance)| *distance <= max_distance)
+                .collect();
+            expected.sort_by(|(a, _, x), (b, _, y)| (x, a).cmp(&(y, b)));
+            assert_eq!(
+                tree.fuzzy_find(path, max_distance),
+                expected,
+                "{:?} {}",
+                path,
+                max_distance
+            );
+        }
+    }
+    assert_eq!(
+        tree.fuzzy_find("/api/usres", 1)
+            .into_iter()
+            .map(|(_, v, d)| (v, d))
+            .collect::<Vec<_>>(),
+        vec![(11, 0)]
+    );
+}
+
+#[test]
+fn case_insensitive_trees() {
+    let mut tree = RadixTree::with_case_folding(CaseFolding::Ascii).unwrap();
+    assert_eq!(tree.case_folding(), CaseFolding::Ascii);
+    tree.insert_many([("/API", 1), ("/api/Users", 2), ("/api/posts", 3), ("/Café", 4)])
+        .unwrap();
+    assert_eq!(tree.len(), 4);
+
+    assert_eq!(tree.find_exact("/api"), Some(1));
+    assert_eq!(tree.find_exact("/API/USERS"), Some(2));
+    assert_eq!(tree.find_exact("/CAFé"), Some(4));
+    // Only ASCII letters are folded.
+    assert_eq!(tree.find_exact("/CAFÉ"), None);
+    let iter = tree.create_iter().unwrap();
+    // Matches return the key as inserted, and the matched part as spelled in the path.
+    let found = tree.longest_prefix(&iter, "/Api/USERS/42").unwrap();
+    assert_eq!((found.value, &*found.key), (2, &b"/api/Users"[..]));
+    assert_eq!((found.matched(), found.remainder()), (&b"/Api/USERS"[..], &b"/42"[..]));
+    // Only a path spelled as the key borrows it.
+    assert!(matches!(
+        tree.longest_prefix(&iter, "/api/Users").unwrap().key,
+        Cow::Owned(_)
+    ));
+    assert!(matches!(
+        tree.longest_prefix(&iter, "/api/posts").unwrap().key,
+        Cow::Borrowed(_)
+    ));
+    let keys: Vec<Vec<u8>> = tree
+        .find_all_prefixes(&iter, "/API/Users/42")
+        .into_iter()
+        .map(|m| m.key.into_owned())
+        .collect();
+    assert_eq!(keys, vec![b"/api/Users".to_vec(), b"/API".to_vec()]);
+
+    // Keys come back as inserted, in the order of their folded form.
+    let keys = |tree: &RadixTree| -> Vec<String> {
+        tree.iter()
+            .unwrap()
+            .map(|(key, _)| String::from_utf8(key).unwrap())
+            .collect()
+    };
+    assert_eq!(keys(&tree), vec!["/API", "/api/posts", "/api/Users", "/Café"]);
+    assert_eq!(tree.complete("/API/", 10)[1].0, b"/api/Users");
+    assert_eq!(tree.fuzzy_find("/api/USER", 1), vec![(b"/api/Users".to_vec(), 2, 1)]);
+    let matched: Vec<_> = tree.find_matching("/API/*S").unwrap().map(|(key, _)| key).collect();
+    assert_eq!(matched, vec![b"/api/posts".to_vec(), b"/api/Users".to_vec()]);
+    assert!(tree.set_weight("/API/USERS", 5));
+    assert_eq!(tree.weight("/api/users"), Some(5));
+    assert_eq!(tree.complete_by_weight("/api/", 1), vec![(b"/api/Users".to_vec(), 2)]);
+    assert!(tree.debug_dump().to_string().contains("key=\"/api/Users\" = 2"));
+
+    // Re-inserting changes the spelling, removing forgets it.
+    tree.insert("/api/users", 5).unwrap();
+    tree.remove("/api").unwrap();
+    assert_eq!(keys(&tree), vec!["/api/posts", "/api/users", "/Café"]);
+    tree.insert("/Api", 6).unwrap();
+    assert_eq!(keys(&tree)[0], "/Api");
+
+    let mut tree = RadixTree::with_case_folding(CaseFolding::Unicode).unwrap();
+    tree.insert_many([("/Café/ΑΒΓ", 1), ("/ΟΔΟΣ", 2), ("/\u{212a}elvin", 3)])
+        .unwrap();
+    assert_eq!(tree.find_exact("/CAFÉ/αβγ"), Some(1));
+    // Final sigma folds to sigma.
+    assert_eq!(tree.find_exact("/οδος"), Some(2));
+    assert_eq!(tree.find_exact("/οδοσ"), Some(2));
+    // Simple folding, including the entries changing the UTF-8 length of a character.
+    assert_eq!(tree.find_exact("/kelvin"), Some(3));
+    assert_eq!(tree.find_exact("/\u{212a}ELVIN"), Some(3));
+    tree.insert("/Stra\u{1e9e}e", 5).unwrap();
+    assert_eq!(tree.find_exact("/straße"), Some(5));
+    assert_eq!(tree.find_exact("/strasse"), None);
+    let iter = tree.create_iter().unwrap();
+    let found = tree.longest_prefix(&iter, "/café/αβγ/δ").unwrap();
+    assert_eq!((found.value, &*found.key), (1, "/Café/ΑΒΓ".as_bytes()));
+    assert_eq!(found.remainder(), "/δ".as_bytes());
+    // The matched prefix is measured in the path, not in the key.
+    let found = tree.longest_prefix(&iter, "/\u{212a}elvin/x").unwrap();
+    assert_eq!((found.value, found.matched_len, found.remainder()), (3, 9, &b"/x"[..]));
+    let found = tree.longest_prefix(&iter, "/STRAßE/x").unwrap();
+    assert_eq!((found.matched(), found.remainder()), ("/STRAßE".as_bytes(), &b"/x"[..]));
+    tree.insert(b"/\xff\xfeX", 4).unwrap();
+    assert_eq!(tree.find_exact(b"/\xff\xfex"), Some(4));
+    assert!(tree.iter().unwrap().any(|(key, idx)| key == b"/\xff\xfeX" && idx == 4));
+
+    // Snapshots keep the case folding and the spellings.
+    let mut buf = Vec::new();
+    tree.write_snapshot(&mut buf).unwrap();
+    let loaded = RadixTree::read_snapshot(&buf[..]).unwrap();
+    assert_eq!(loaded.case_folding(), CaseFolding::Unicode);
+    assert_eq!(loaded.find_exact("/KELVIN"), Some(3));
+    assert_eq!(
+        loaded.iter().unwrap().collect::<Vec<_>>(),
+        tree.iter().unwrap().collect::<Vec<_>>()
+    );
+    let mut bad_flags = buf.clone();
+    bad_flags[6] = 3;
+    assert!(RadixTree::read_snapshot(&bad_flags[..]).is_err());
+
+    // Flattened trees fold paths too, and spell match keys as in the path.
+    let mut buf = Vec::new();
+    tree.write_flat(&mut buf).unwrap();
+    let flat = FlatTree::new(&buf[..]).unwrap();
+    assert_eq!(flat.case_folding(), CaseFolding::Unicode);
+    assert_eq!(flat.find_exact("/CAFÉ/αβγ"), Some(1));
+    assert_eq!(flat.find_exact("/strasse"), None);
+    let found = flat.longest_prefix("/\u{212a}ELVIN/x").unwrap();
+    assert_eq!((found.value, &*found.key), (3, "/\u{212a}ELVIN".as_bytes()));
+    assert_eq!(found.remainder(), b"/x");
+    let values: Vec<i32> = flat.find_all_prefixes("/STRAßE").into_iter().map(|m| m.value).collect();
+    assert_eq!(values, vec![5]);
+    let mut bad_flags = buf.clone();
+    bad_flags[6] = 3;
+    assert!(FlatTree::new(&bad_flags[..]).is_err());
+}
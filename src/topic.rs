@@ -0,0 +1,198 @@
+//! MQTT topic filter matching built on the Radix Tree
+//!
+//! Topic filters are stored as they are written, wildcards included. Matching a topic
+//! walks the tree one topic level at a time, following at every level both the literal
+//! level and the `+` wildcard, and collecting the `#` wildcards met on the way, so that
+//! only the filters sharing a path with the topic are visited.
+
+use crate::node::Cursor;
+use crate::radix_tree::RadixTree;
+use crate::slab::Slab;
+use std::fmt;
+
+/// Maximum length of a topic or topic filter, as encoded in MQTT packets.
+const MAX_TOPIC_LEN: usize = 65535;
+
+/// Error returned for invalid topic filters, or when a [`TopicMatcher`] cannot be updated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopicError {
+    /// A topic filter is empty, too long, or misuses a wildcard.
+    InvalidFilter(String),
+    /// The underlying tree rejected the insertion.
+    Tree(i32),
+}
+
+impl fmt::Display for TopicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopicError::InvalidFilter(filter) => write!(f, "invalid topic filter {:?}", filter),
+            TopicError::Tree(rc) => write!(f, "radix tree insertion failed with error {}", rc),
+        }
+    }
+}
+
+impl std::error::Error for TopicError {}
+
+/// Checks the rules common to topics and filters: non-empty, at most 65535 bytes and
+/// without NUL characters.
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= MAX_TOPIC_LEN && !name.contains('\0')
+}
+
+/// Checks that `+` and `#` only appear as whole levels, and `#` only as the last one.
+fn valid_filter(filter: &str) -> bool {
+    let levels: Vec<&str> = filter.split('/').collect();
+    valid_name(filter)
+        && levels.iter().enumerate().all(|(i, level)| match *level {
+            "+" => true,
+            "#" => i == levels.len() - 1,
+            _ => !level.contains(['+', '#']),
+        })
+}
+
+/// A set of MQTT topic filters, each with a value, matching published topics to all
+/// their subscriptions.
+///
+/// Filters follow the MQTT rules: `+` matches exactly one topic level and `#`, which must
+/// be the last level, matches any number of levels, including none (`sport/#` matches
+/// `sport`). Topics starting with `$`, such as `$SYS/broker/load`, are not matched by
+/// filters starting with a wildcard, only by filters spelling out their first level.
+///
+/// # Examples
+///
+/// ```
+/// use radix_route_matcher::TopicMatcher;
+///
+/// let mut subscriptions = TopicMatcher::new().unwrap();
+/// subscriptions.insert("sensors/+/temp", "thermostat").unwrap();
+/// subscriptions.insert("devices/#", "logger").unwrap();
+/// subscriptions.insert("#", "audit").unwrap();
+///
+/// let mut matched: Vec<_> = subscriptions.matches("sensors/kitchen/temp").into_iter().map(|(_, v)| *v).collect();
+/// matched.sort();
+/// assert_eq!(matched, vec!["audit", "thermostat"]);
+/// assert_eq!(subscriptions.matches("devices").len(), 2);
+/// assert!(subscriptions.matches("$SYS/broker/uptime").is_empty());
+/// ```
+pub struct TopicMatcher<V> {
+    tree: RadixTree,
+    entries: Slab<(Box<str>, V)>,
+}
+
+impl<V> TopicMatcher<V> {
+    /// Creates a new empty matcher.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if memory allocation fails.
+    pub fn new() -> Result<Self, &'static str> {
+        Ok(Self {
+            tree: RadixTree::new()?,
+            entries: Slab::new(),
+        })
+    }
+
+    /// Returns the number of filters in the matcher.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the matcher holds no filter.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts a topic filter, returning the value it previously had, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TopicError::InvalidFilter`] if the filter is empty, longer than 65535
+    /// bytes, contains a NUL character or misuses a wildcard, or [`TopicError::Tree`] if
+    /// the tree insertion fails (on allocation failure).
+    pub fn insert(&mut self, filter: &str, value: V) -> Result<Option<V>, TopicError> {
+        if !valid_filter(filter) {
+            return Err(TopicError::InvalidFilter(filter.to_string()));
+        }
+        if let Some(slot) = self.tree.find_exact(filter) {
+            let entry = self.entries.get_mut(slot).unwrap();
+            return Ok(Some(std::mem::replace(&mut entry.1, value)));
+        }
+        self.tree
+            .insert(filter, self.entries.next_value())
+            .map_err(TopicError::Tree)?;
+        self.entries.insert((filter.into(), value));
+        Ok(None)
+    }
+
+    /// Returns the value of a topic filter.
+    pub fn get(&self, filter: &str) -> Option<&V> {
+        let slot = self.tree.find_exact(filter)?;
+        self.entries.get(slot).map(|(_, value)| value)
+    }
+
+    /// Removes a topic filter, returning its value.
+    pub fn remove(&mut self, filter: &str) -> Option<V> {
+        let slot = self.tree.find_exact(filter)?;
+        self.tree.remove(filter).ok()?;
+        self.entries.remove(slot).map(|(_, value)| value)
+    }
+
+    /// Returns all the filters matching `topic` with their values, in no particular order.
+    ///
+    /// Topics are published names: they must not contain wildcards. Invalid topics match
+    /// nothing.
+    pub fn matches(&self, topic: &str) -> Vec<(&str, &V)> {
+        let mut slots = Vec::new();
+        if valid_name(topic) && !topic.contains(['+', '#']) {
+            let levels: Vec<&str> = topic.split('/').collect();
+            let wildcards = !topic.starts_with('$');
+            self.match_level(Cursor::new(&self.tree), &levels, wildcards, &mut slots);
+        }
+        slots
+            .into_iter()
+            .filter_map(|slot| self.entries.get(slot))
+            .map(|(filter, value)| (&**filter, value))
+            .collect()
+    }
+
+    /// Returns `true` if any filter matches `topic`.
+    pub fn has_match(&self, topic: &str) -> bool {
+        !self.matches(topic).is_empty()
+    }
+
+    /// Returns all the filters with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.entries.iter().map(|(filter, value)| (&**filter, value))
+    }
+
+    /// Collects the filters matching `levels` from `cursor`, which is at the start of the
+    /// first of them. `wildcards` is `false` when the level must be matched literally.
+    fn match_level(&self, cursor: Cursor<'_>, levels: &[&str], wildcards: bool, slots: &mut Vec<i32>) {
+        let Some((level, rest)) = levels.split_first() else {
+            return;
+        };
+        if wildcards {
+            if let Some(slot) = cursor.step(b'#').and_then(|c| c.value()) {
+                slots.push(slot);
+            }
+            if let Some(cursor) = cursor.step(b'+') {
+                self.match_rest(cursor, rest, slots);
+            }
+        }
+        if let Some(cursor) = cursor.walk(level.as_bytes()) {
+            self.match_rest(cursor, rest, slots);
+        }
+    }
+
+    /// Collects the filters matching the remaining `levels` from `cursor`, which is at the
+    /// end of a level.
+    fn match_rest(&self, cursor: Cursor<'_>, levels: &[&str], slots: &mut Vec<i32>) {
+        if levels.is_empty() {
+            // The topic ends here: match the filters ending here too, or with a `/#` level.
+            slots.extend(cursor.value());
+            slots.extend(cursor.walk(b"/#").and_then(|c| c.value()));
+        } else if let Some(cursor) = cursor.step(b'/') {
+            self.match_level(cursor, levels, true, slots);
+        }
+    }
+}
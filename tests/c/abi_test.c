@@ -0,0 +1,134 @@
+/*
+ * Exercises the C API through the generated header, linked against the cdylib.
+ * Exits with a non-zero status, after printing the failed check, on error.
+ */
+
+#ifdef __linux__
+#define _GNU_SOURCE
+#include <dlfcn.h>
+#endif
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "radix_route_matcher.h"
+
+#define CHECK(cond)                                                            \
+    do {                                                                       \
+        if (!(cond)) {                                                         \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+                    #cond);                                                    \
+            return 1;                                                          \
+        }                                                                      \
+    } while (0)
+
+struct visit {
+    int values[8];
+    int count;
+    int stop_after;
+};
+
+static int record(const unsigned char *key, unsigned long len, int value, void *userdata) {
+    struct visit *visit = userdata;
+    (void)key;
+    (void)len;
+    visit->values[visit->count++] = value;
+    return visit->count == visit->stop_after;
+}
+
+#define KEY(s) (const unsigned char *)(s), (unsigned long)strlen(s)
+
+int main(void) {
+    const char *path = "/api/users/1";
+
+    CHECK(radix_tree_abi_version() == RADIX_TREE_ABI_VERSION);
+    CHECK(strlen(radix_tree_version_string()) > 0);
+    (void)radix_tree_features();
+#ifdef __linux__
+#ifdef RADIX_ROUTE_MATCHER_SYMBOL_VERSIONS
+    /* Exports carry the symbol version of the ABI they belong to. */
+    int (*abi_version)(void) = (int (*)(void))dlvsym(RTLD_DEFAULT, "radix_tree_abi_version", "RADIX_ROUTE_MATCHER_1");
+    CHECK(abi_version != NULL && abi_version() == RADIX_TREE_ABI_VERSION);
+#endif
+    /* The allocator hooks of the embedded rax code are not part of the API. */
+    CHECK(dlsym(RTLD_DEFAULT, "rax_rust_malloc") == NULL);
+#endif
+
+    radix_tree_t *tree = radix_tree_new();
+    CHECK(tree != NULL);
+    unsigned long empty_bytes = 0, bytes = 0;
+    CHECK(radix_tree_allocated_bytes(tree, &empty_bytes) == 0);
+    CHECK(empty_bytes > 0);
+
+    CHECK(radix_tree_insert(tree, KEY("/"), 1) >= 0);
+    CHECK(radix_tree_insert(tree, KEY("/api"), 2) >= 0);
+    CHECK(radix_tree_insert(tree, KEY("/api/users"), 3) >= 0);
+    CHECK(radix_tree_insert(tree, KEY("/static"), 4) >= 0);
+
+    CHECK(radix_tree_find(tree, KEY("/api")) == (void *)2);
+    CHECK(radix_tree_find(tree, KEY("/api/posts")) == NULL);
+    CHECK(radix_tree_remove(tree, KEY("/static")) == 1);
+    CHECK(radix_tree_find(tree, KEY("/static")) == NULL);
+    CHECK(radix_tree_allocated_bytes(tree, &bytes) == 0);
+    CHECK(bytes > empty_bytes);
+
+    /* Invalid arguments are reported, with a message. */
+    CHECK(radix_tree_insert(tree, NULL, 3, 5) == RADIX_TREE_EINVAL);
+    CHECK(radix_tree_last_error() != NULL);
+    CHECK(strstr(radix_tree_last_error(), "radix_tree_insert") != NULL);
+
+    /* Walk every prefix of the path, longest first. */
+    radix_iter_t *it = radix_tree_new_it(tree);
+    CHECK(it != NULL);
+    CHECK(radix_tree_search(tree, it, KEY(path)) == it);
+    CHECK(radix_tree_prev(it, KEY(path)) == 3);
+    CHECK(radix_tree_prev(it, KEY(path)) == 2);
+    CHECK(radix_tree_prev(it, KEY(path)) == 1);
+    CHECK(radix_tree_prev(it, KEY(path)) == -1);
+
+    /* After a search, the first key returned going forward is the longest prefix. */
+    CHECK(radix_tree_search(tree, it, KEY(path)) == it);
+    CHECK(radix_tree_next(it, KEY(path)) == 3);
+    CHECK(radix_tree_next(it, KEY(path)) == -1);
+
+    CHECK(radix_tree_search(tree, it, KEY(path)) == it);
+    CHECK(radix_tree_up(it, KEY(path)) == 3);
+
+    /* Callback-based iteration, in a single call. */
+    struct visit visit = {{0}, 0, 0};
+    CHECK(radix_tree_foreach_prefix(tree, KEY(path), record, &visit) == 3);
+    CHECK(visit.values[0] == 3 && visit.values[1] == 2 && visit.values[2] == 1);
+
+    struct visit first = {{0}, 0, 1};
+    CHECK(radix_tree_foreach(tree, KEY("/api"), record, &first) == 1);
+    CHECK(first.values[0] == 2);
+    CHECK(radix_tree_foreach(tree, NULL, 0, NULL, NULL) == RADIX_TREE_EINVAL);
+
+    /* Batches. */
+    const unsigned char *batch_keys[] = {(const unsigned char *)"/v1", (const unsigned char *)"/v1/a"};
+    unsigned long batch_lens[] = {3, 5};
+    int batch_idxs[] = {10, 11};
+    CHECK(radix_tree_insert_many(tree, batch_keys, batch_lens, batch_idxs, 2) == 2);
+
+    const unsigned char *batch_paths[] = {(const unsigned char *)"/v1/a/b", (const unsigned char *)"/api/x"};
+    unsigned long path_lens[] = {7, 6};
+    int results[2];
+    CHECK(radix_tree_match_many(tree, batch_paths, path_lens, 2, results) == 2);
+    CHECK(results[0] == 11 && results[1] == 2);
+
+    /* Stopped iterators fail on use, and are still freed by the caller. */
+    radix_iter_t *stopped = radix_tree_new_it(tree);
+    CHECK(stopped != NULL);
+    CHECK(radix_tree_stop(stopped) == 0);
+    CHECK(radix_tree_prev(stopped, KEY(path)) == -1);
+    free(stopped);
+
+    /* Iterators may outlive their tree. */
+    CHECK(radix_tree_destroy(tree) == 0);
+    CHECK(radix_tree_up(it, KEY(path)) == -1);
+    CHECK(radix_tree_free_it(it) == 0);
+
+    printf("ok\n");
+    return 0;
+}
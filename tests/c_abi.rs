@@ -0,0 +1,64 @@
+//! Compiles `tests/c/abi_test.c` against the generated header, links it against the cdylib
+//! and runs it.
+
+#![cfg(unix)]
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Returns `target/<profile>`, from the path of this test binary (`target/<profile>/deps/...`).
+fn profile_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn c_program_links_against_cdylib() {
+    let profile = profile_dir();
+    let deps = profile.join("deps");
+    let include = profile.join("include");
+    assert!(
+        include.join("radix_route_matcher.h").is_file(),
+        "missing generated header"
+    );
+
+    let pc = fs::read_to_string(profile.join("pkgconfig/radix_route_matcher.pc")).expect("missing pkg-config file");
+    assert!(pc.contains(&format!("prefix={}\n", profile.display())));
+    assert!(pc.contains("Libs: -L${libdir} -lradix_route_matcher\n"));
+
+    let exe = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("abi_test");
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/c/abi_test.c"))
+        .arg(format!("-I{}", include.display()))
+        .arg(format!("-L{}", deps.display()))
+        .arg("-lradix_route_matcher")
+        .arg(format!("-Wl,-rpath,{}", deps.display()))
+        .args(if cfg!(target_os = "linux") { &["-ldl"][..] } else { &[] })
+        // Set when build.rs linked the library with its symbol versions.
+        .args(match option_env!("RADIX_ROUTE_MATCHER_SYMBOL_VERSIONS") {
+            Some("1") => &["-DRADIX_ROUTE_MATCHER_SYMBOL_VERSIONS"][..],
+            _ => &[],
+        })
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "C test program failed to compile");
+
+    // cargo puts target/<profile> on the library path, where `cargo build` may have left an
+    // older copy of the library that would take precedence over the rpath.
+    let output = Command::new(&exe)
+        .env("LD_LIBRARY_PATH", &deps)
+        .output()
+        .expect("failed to run the C test program");
+    assert!(
+        output.status.success(),
+        "C test program failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(output.stdout, b"ok\n");
+}